@@ -29,7 +29,7 @@ async fn run() -> Result<(), Box<dyn error::Error>> {
         .await?
         .map_err(|(status_code, err_json)| format!("{status_code} {err_json:?}"))?;
 
-    println!("app_access_token value:{app_access_token}");
+    println!("app_access_token value:{}", app_access_token.reveal());
 
     assert_eq!(app_access_token.app_id_and_app_secret().unwrap().0, app_id);
 
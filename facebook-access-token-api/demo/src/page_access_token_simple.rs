@@ -42,7 +42,8 @@ async fn run() -> Result<(), Box<dyn error::Error>> {
             .map_err(|(status_code, err_json)| format!("{status_code} {err_json:?}"))?;
 
     println!(
-        "page_session_info_access_token value:{page_session_info_access_token} expires_in:{page_session_info_access_token_expires_in:?}"
+        "page_session_info_access_token value:{} expires_in:{page_session_info_access_token_expires_in:?}",
+        page_session_info_access_token.reveal()
     );
 
     //
@@ -51,7 +51,8 @@ async fn run() -> Result<(), Box<dyn error::Error>> {
         .map_err(|(status_code, err_json)| format!("{status_code} {err_json:?}"))?;
 
     println!(
-        "long_lived_user_access_token value:{long_lived_user_access_token} expires_in:{long_lived_user_access_token_expires_in:?}"
+        "long_lived_user_access_token value:{} expires_in:{long_lived_user_access_token_expires_in:?}",
+        long_lived_user_access_token.reveal()
     );
 
     //
@@ -83,7 +84,8 @@ async fn run() -> Result<(), Box<dyn error::Error>> {
             .map_err(|(status_code, err_json)| format!("{status_code} {err_json:?}"))?;
 
     println!(
-        "user_session_info_access_token value:{user_session_info_access_token} expires_in:{user_session_info_access_token_expires_in:?}"
+        "user_session_info_access_token value:{} expires_in:{user_session_info_access_token_expires_in:?}",
+        user_session_info_access_token.reveal()
     );
 
     //
@@ -0,0 +1,167 @@
+//! [Ref](https://developers.facebook.com/docs/development/create-an-app/app-dashboard/data-deletion-callback)
+
+use facebook_access_token::secret::redact;
+use http_api_client::async_trait;
+use serde::{Deserialize, Serialize};
+use url::{ParseError as UrlParseError, Url};
+
+use crate::{
+    callback::{parse_signed_request_from_form_body, CallbackError},
+    signed_request::SignedRequestPayload,
+};
+
+//
+#[async_trait]
+pub trait UserDataDeleter {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    // Returns the confirmation_code, the deletion itself may finish later.
+    async fn delete_user_data(
+        &self,
+        user_id: u64,
+        payload: &SignedRequestPayload,
+    ) -> Result<String, Self::Error>;
+}
+
+//
+#[derive(Clone)]
+pub struct DataDeletionCallbackHandler<D> {
+    deleter: D,
+    app_secret: Box<str>,
+    status_url: Url,
+}
+
+impl<D: core::fmt::Debug> core::fmt::Debug for DataDeletionCallbackHandler<D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DataDeletionCallbackHandler")
+            .field("deleter", &self.deleter)
+            .field("app_secret", &redact(&self.app_secret))
+            .field("status_url", &self.status_url)
+            .finish()
+    }
+}
+
+impl<D> DataDeletionCallbackHandler<D>
+where
+    D: UserDataDeleter + Send + Sync,
+{
+    // status_url is where the user can check the deletion status, the confirmation_code is appended as `id`.
+    // It is parsed here so that handle cannot fail after the user data is deleted.
+    pub fn new(
+        deleter: D,
+        app_secret: impl AsRef<str>,
+        status_url: impl AsRef<str>,
+    ) -> Result<Self, UrlParseError> {
+        Ok(Self {
+            deleter,
+            app_secret: app_secret.as_ref().into(),
+            status_url: Url::parse(status_url.as_ref())?,
+        })
+    }
+
+    pub async fn handle(
+        &self,
+        body: &[u8],
+    ) -> Result<DataDeletionCallbackResponseBody, CallbackError<D::Error>> {
+        let (user_id, signed_request) =
+            parse_signed_request_from_form_body(body, &self.app_secret)?;
+
+        let confirmation_code = self
+            .deleter
+            .delete_user_data(user_id, &signed_request.payload)
+            .await
+            .map_err(CallbackError::HandlerFailed)?;
+
+        let mut url = self.status_url.to_owned();
+        url.query_pairs_mut().append_pair("id", &confirmation_code);
+
+        Ok(DataDeletionCallbackResponseBody {
+            url: url.into(),
+            confirmation_code,
+        })
+    }
+}
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataDeletionCallbackResponseBody {
+    pub url: String,
+    pub confirmation_code: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Error as IoError;
+
+    use futures_lite::future::block_on;
+
+    use crate::callback::tests_helper::FORM_BODY;
+
+    #[derive(Debug)]
+    struct MyDeleter;
+
+    #[async_trait]
+    impl UserDataDeleter for MyDeleter {
+        type Error = IoError;
+
+        async fn delete_user_data(
+            &self,
+            user_id: u64,
+            _payload: &SignedRequestPayload,
+        ) -> Result<String, Self::Error> {
+            Ok(format!("del_{user_id}"))
+        }
+    }
+
+    #[test]
+    fn test_handle() {
+        block_on(async {
+            let handler = DataDeletionCallbackHandler::new(
+                MyDeleter,
+                "APP_SECRET",
+                "https://example.com/deletion",
+            )
+            .unwrap();
+
+            let body = handler.handle(FORM_BODY.as_bytes()).await.unwrap();
+            assert_eq!(
+                body,
+                DataDeletionCallbackResponseBody {
+                    url: "https://example.com/deletion?id=del_123".into(),
+                    confirmation_code: "del_123".into(),
+                }
+            );
+            assert_eq!(
+                serde_json::to_string(&body).unwrap(),
+                r#"{"url":"https://example.com/deletion?id=del_123","confirmation_code":"del_123"}"#
+            );
+
+            match handler.handle(b"foo=bar").await {
+                Err(CallbackError::SignedRequestMissing) => {}
+                x => panic!("{x:?}"),
+            }
+
+            let handler = DataDeletionCallbackHandler::new(
+                MyDeleter,
+                "OTHER_SECRET",
+                "https://example.com/deletion",
+            )
+            .unwrap();
+            match handler.handle(FORM_BODY.as_bytes()).await {
+                Err(CallbackError::SignedRequestInvalid(_)) => {}
+                x => panic!("{x:?}"),
+            }
+
+            assert!(
+                DataDeletionCallbackHandler::new(MyDeleter, "APP_SECRET", "/deletion").is_err()
+            );
+
+            let handler =
+                DataDeletionCallbackHandler::new(MyDeleter, "APP_SECRET", "https://example.com/")
+                    .unwrap();
+            assert!(!format!("{handler:?}").contains("APP_SECRET"));
+        })
+    }
+}
@@ -0,0 +1,98 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/advanced/manual-flow#deauth-callback)
+
+use facebook_access_token::secret::redact;
+use http_api_client::async_trait;
+
+use crate::{
+    callback::{parse_signed_request_from_form_body, CallbackError},
+    signed_request::SignedRequestPayload,
+};
+
+//
+#[async_trait]
+pub trait DeauthorizeListener {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn on_deauthorize(
+        &self,
+        user_id: u64,
+        payload: &SignedRequestPayload,
+    ) -> Result<(), Self::Error>;
+}
+
+// Facebook only needs a 200 response, handle returns the user_id.
+#[derive(Clone)]
+pub struct DeauthorizeCallbackHandler<L> {
+    listener: L,
+    app_secret: Box<str>,
+}
+
+impl<L: core::fmt::Debug> core::fmt::Debug for DeauthorizeCallbackHandler<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeauthorizeCallbackHandler")
+            .field("listener", &self.listener)
+            .field("app_secret", &redact(&self.app_secret))
+            .finish()
+    }
+}
+
+impl<L> DeauthorizeCallbackHandler<L>
+where
+    L: DeauthorizeListener + Send + Sync,
+{
+    pub fn new(listener: L, app_secret: impl AsRef<str>) -> Self {
+        Self {
+            listener,
+            app_secret: app_secret.as_ref().into(),
+        }
+    }
+
+    pub async fn handle(&self, body: &[u8]) -> Result<u64, CallbackError<L::Error>> {
+        let (user_id, signed_request) =
+            parse_signed_request_from_form_body(body, &self.app_secret)?;
+
+        self.listener
+            .on_deauthorize(user_id, &signed_request.payload)
+            .await
+            .map_err(CallbackError::HandlerFailed)?;
+
+        Ok(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{io::Error as IoError, sync::Mutex};
+
+    use futures_lite::future::block_on;
+
+    use crate::callback::tests_helper::FORM_BODY;
+
+    #[derive(Default)]
+    struct MyListener(Mutex<Vec<u64>>);
+
+    #[async_trait]
+    impl DeauthorizeListener for MyListener {
+        type Error = IoError;
+
+        async fn on_deauthorize(
+            &self,
+            user_id: u64,
+            _payload: &SignedRequestPayload,
+        ) -> Result<(), Self::Error> {
+            self.0.lock().unwrap().push(user_id);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_handle() {
+        block_on(async {
+            let handler = DeauthorizeCallbackHandler::new(MyListener::default(), "APP_SECRET");
+            assert_eq!(handler.handle(FORM_BODY.as_bytes()).await.unwrap(), 123);
+            assert_eq!(*handler.listener.0.lock().unwrap(), vec![123]);
+        })
+    }
+}
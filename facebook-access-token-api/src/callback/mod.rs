@@ -0,0 +1,70 @@
+//! Framework-agnostic handlers for the callbacks Facebook posts a signed_request to.
+
+use url::form_urlencoded;
+
+use crate::signed_request::{SignedRequest, SignedRequestError};
+
+//
+pub mod data_deletion;
+
+pub use data_deletion::{
+    DataDeletionCallbackHandler, DataDeletionCallbackResponseBody, UserDataDeleter,
+};
+
+//
+pub mod deauthorize;
+
+pub use deauthorize::{DeauthorizeCallbackHandler, DeauthorizeListener};
+
+//
+// body is the raw application/x-www-form-urlencoded request body.
+fn parse_signed_request_from_form_body<E>(
+    body: &[u8],
+    app_secret: &str,
+) -> Result<(u64, SignedRequest), CallbackError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let raw = form_urlencoded::parse(body)
+        .find(|(k, _)| k == "signed_request")
+        .map(|(_, v)| v)
+        .ok_or(CallbackError::SignedRequestMissing)?;
+
+    let signed_request = SignedRequest::parse_and_verify(raw, app_secret)
+        .map_err(CallbackError::SignedRequestInvalid)?;
+    let user_id = signed_request
+        .payload
+        .user_id
+        .ok_or(CallbackError::UserIdMissing)?;
+
+    Ok((user_id, signed_request))
+}
+
+//
+#[derive(Debug)]
+pub enum CallbackError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    SignedRequestMissing,
+    SignedRequestInvalid(SignedRequestError),
+    UserIdMissing,
+    HandlerFailed(E),
+}
+
+impl<E> core::fmt::Display for CallbackError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<E> std::error::Error for CallbackError<E> where E: std::error::Error + Send + Sync + 'static {}
+
+#[cfg(test)]
+pub(crate) mod tests_helper {
+    // user_id 123, signed with APP_SECRET
+    pub(crate) const FORM_BODY: &str = "signed_request=Fa5Y_8gSHSmMqAGJpoiCX1RXwJ9aSy0IoQuW3xcTsNo.eyJhbGdvcml0aG0iOiJITUFDLVNIQTI1NiIsImV4cGlyZXMiOjEyOTE4NDA0MDAsImlzc3VlZF9hdCI6MTI5MTgzNjgwMCwib2F1dGhfdG9rZW4iOiJUT0tFTiIsInVzZXJfaWQiOiIxMjMiLCJjb2RlIjoiQ09ERSJ9";
+}
@@ -1,5 +1,9 @@
 //! [Ref](https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#get-a-long-lived-user-access-token)
 
+use chrono::{DateTime, Utc};
+use facebook_access_token::{secret::redact, AccessTokenLifetime, AccessTokenWithMetadata};
+#[cfg(feature = "usage")]
+use facebook_graph_api_rate_limit::Usage;
 use http_api_client_endpoint::{
     http::{
         header::{ACCEPT, USER_AGENT},
@@ -19,16 +23,37 @@ use crate::{
 };
 
 //
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AccessTokenEndpoint {
     pub grant_type: Box<str>,
     pub app_id: u64,
     pub app_secret: Option<Box<str>>,
     pub fb_exchange_token: Option<Box<str>>,
+    pub redirect_uri: Option<Box<str>>,
+    pub code: Option<Box<str>>,
+    pub code_verifier: Option<Box<str>>,
     //
     pub version: Option<Box<str>>,
 }
 
+impl core::fmt::Debug for AccessTokenEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AccessTokenEndpoint")
+            .field("grant_type", &self.grant_type)
+            .field("app_id", &self.app_id)
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field(
+                "fb_exchange_token",
+                &self.fb_exchange_token.as_deref().map(redact),
+            )
+            .field("redirect_uri", &self.redirect_uri)
+            .field("code", &self.code.as_deref().map(redact))
+            .field("code_verifier", &self.code_verifier.as_deref().map(redact))
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
 impl AccessTokenEndpoint {
     pub fn new(
         grant_type: impl AsRef<str>,
@@ -42,9 +67,27 @@ impl AccessTokenEndpoint {
             app_id,
             app_secret: app_secret.into(),
             fb_exchange_token: fb_exchange_token.into(),
+            redirect_uri: None,
+            code: None,
+            code_verifier: None,
             version: version.into(),
         }
     }
+
+    pub fn redirect_uri(mut self, value: impl AsRef<str>) -> Self {
+        self.redirect_uri = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn code(mut self, value: impl AsRef<str>) -> Self {
+        self.code = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn code_verifier(mut self, value: impl AsRef<str>) -> Self {
+        self.code_verifier = Some(value.as_ref().into());
+        self
+    }
 }
 
 impl Endpoint for AccessTokenEndpoint {
@@ -77,6 +120,20 @@ impl Endpoint for AccessTokenEndpoint {
                 .append_pair("fb_exchange_token", fb_exchange_token);
         }
 
+        if let Some(redirect_uri) = &self.redirect_uri {
+            url.query_pairs_mut()
+                .append_pair("redirect_uri", redirect_uri);
+        }
+
+        if let Some(code) = &self.code {
+            url.query_pairs_mut().append_pair("code", code);
+        }
+
+        if let Some(code_verifier) = &self.code_verifier {
+            url.query_pairs_mut()
+                .append_pair("code_verifier", code_verifier);
+        }
+
         let request = Request::builder()
             .method(Method::GET)
             .uri(url.as_str())
@@ -93,13 +150,26 @@ impl Endpoint for AccessTokenEndpoint {
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
         let status = response.status();
+        #[cfg(feature = "usage")]
+        let usage = Usage::from_headers(response.headers());
         match status {
-            StatusCode::OK => Ok(EndpointRet::Ok(
-                serde_json::from_slice(response.body())
-                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
-            )),
+            StatusCode::OK => {
+                let ok_json: AccessTokenResponseBodyOkJson =
+                    serde_json::from_slice(response.body())
+                        .map_err(EndpointError::DeResponseBodyOkJsonFailed)?;
+                #[cfg(feature = "usage")]
+                let ok_json = AccessTokenResponseBodyOkJson { usage, ..ok_json };
+                Ok(EndpointRet::Ok(ok_json))
+            }
             status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
-                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Ok(err_json) => {
+                    #[cfg(feature = "usage")]
+                    let err_json = ResponseBodyErrJson {
+                        usage: usage.into(),
+                        ..err_json
+                    };
+                    Ok(EndpointRet::Other((status, Ok(err_json))))
+                }
                 Err(_) => Ok(EndpointRet::Other((
                     status,
                     Err(response.body().to_owned()),
@@ -117,4 +187,121 @@ pub struct AccessTokenResponseBodyOkJson {
     pub access_token: String,
     pub token_type: String,
     pub expires_in: Option<usize>,
+    //
+    #[cfg(feature = "usage")]
+    #[serde(skip)]
+    pub usage: Usage,
+}
+
+impl AccessTokenResponseBodyOkJson {
+    pub fn to_access_token_with_metadata<T>(
+        &self,
+        issued_at: DateTime<Utc>,
+    ) -> AccessTokenWithMetadata<T>
+    where
+        T: From<String> + AccessTokenLifetime,
+    {
+        AccessTokenWithMetadata::with_expires_in(
+            self.access_token.to_owned().into(),
+            issued_at,
+            self.expires_in.map(Into::into),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+    use facebook_access_token::{LongLivedUserAccessToken, LONG_LIVED_USER_ACCESS_TOKEN_LIFETIME};
+
+    #[test]
+    fn test_endpoint_render_request() {
+        let ep = AccessTokenEndpoint::new(
+            "fb_exchange_token",
+            123,
+            Some("APP_SECRET".into()),
+            Some("TOKEN".into()),
+            None,
+        );
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/oauth/access_token?grant_type=fb_exchange_token&client_id=123&client_secret=APP_SECRET&fb_exchange_token=TOKEN"
+        );
+
+        let ep = AccessTokenEndpoint::new(
+            "authorization_code",
+            123,
+            Some("APP_SECRET".into()),
+            None,
+            None,
+        )
+        .redirect_uri("https://example.com/cb")
+        .code("CODE");
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/oauth/access_token?grant_type=authorization_code&client_id=123&client_secret=APP_SECRET&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&code=CODE"
+        );
+
+        let ep = AccessTokenEndpoint::new("authorization_code", 123, None, None, None)
+            .redirect_uri("https://example.com/cb")
+            .code("CODE")
+            .code_verifier("CODE_VERIFIER");
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/oauth/access_token?grant_type=authorization_code&client_id=123&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&code=CODE&code_verifier=CODE_VERIFIER"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_debug() {
+        let ep = AccessTokenEndpoint::new(
+            "fb_exchange_token",
+            123,
+            Some("APP_SECRET".into()),
+            Some("TOKEN".into()),
+            None,
+        );
+        let debug = format!("{ep:?}");
+        assert!(debug.contains(r#"grant_type: "fb_exchange_token""#));
+        assert!(!debug.contains("APP_SECRET"));
+        assert!(!debug.contains(r#""TOKEN""#));
+    }
+
+    #[test]
+    fn test_to_access_token_with_metadata() {
+        let issued_at = Utc.timestamp_opt(1668750788, 0).unwrap();
+
+        let ok_json = serde_json::from_str::<AccessTokenResponseBodyOkJson>(
+            r#"{"access_token":"TOKEN","token_type":"bearer","expires_in":5183944}"#,
+        )
+        .unwrap();
+        let t = ok_json.to_access_token_with_metadata::<LongLivedUserAccessToken>(issued_at);
+        assert_eq!(t.token.inner(), "TOKEN");
+        assert_eq!(t.issued_at, Some(issued_at));
+        assert_eq!(
+            t.expires_at,
+            Utc.timestamp_opt(1668750788 + 5183944, 0).single()
+        );
+        assert!(!t.expires_at_is_estimated);
+
+        let ok_json = serde_json::from_str::<AccessTokenResponseBodyOkJson>(
+            r#"{"access_token":"TOKEN","token_type":"bearer"}"#,
+        )
+        .unwrap();
+        let t = ok_json.to_access_token_with_metadata::<LongLivedUserAccessToken>(issued_at);
+        assert_eq!(
+            t.expires_at,
+            Some(
+                issued_at
+                    + chrono::Duration::from_std(LONG_LIVED_USER_ACCESS_TOKEN_LIFETIME).unwrap()
+            )
+        );
+        assert!(t.expires_at_is_estimated);
+    }
 }
@@ -0,0 +1,213 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/reference/user/accounts/)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use facebook_graph_api_object_paging::cursor_based_pagination::Paging;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::{Account, ResponseBodyErrJson},
+};
+
+//
+#[derive(Clone)]
+pub struct AccountsEndpoint {
+    pub user_id: Option<u64>,
+    pub limit: Option<usize>,
+    pub after: Option<Box<str>>,
+    //
+    pub access_token: Box<str>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for AccountsEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AccountsEndpoint")
+            .field("user_id", &self.user_id)
+            .field("limit", &self.limit)
+            .field("after", &self.after)
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl AccountsEndpoint {
+    pub fn new(access_token: impl AsRef<str>, version: impl Into<Option<Box<str>>>) -> Self {
+        Self {
+            user_id: None,
+            limit: None,
+            after: None,
+            access_token: access_token.as_ref().into(),
+            app_secret: None,
+            app_secret_time: None,
+            version: version.into(),
+        }
+    }
+
+    // Default is me.
+    pub fn user_id(mut self, value: u64) -> Self {
+        self.user_id = Some(value);
+        self
+    }
+
+    pub fn limit(mut self, value: usize) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    pub fn after(mut self, value: impl AsRef<str>) -> Self {
+        self.after = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for AccountsEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<AccountsResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/{}/accounts?fields={}",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+            self.user_id
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| "me".into()),
+            Account::fields(),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+        if let Some(app_secret) = &self.app_secret {
+            url.query_pairs_mut().extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
+        if let Some(limit) = self.limit {
+            url.query_pairs_mut()
+                .append_pair("limit", limit.to_string().as_str());
+        }
+        if let Some(after) = &self.after {
+            url.query_pairs_mut().append_pair("after", after);
+        }
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AccountsResponseBodyOkJson {
+    pub data: Vec<Account>,
+    pub paging: Option<Paging>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = AccountsEndpoint::new("TOKEN", None)
+            .limit(10)
+            .after("AFTER");
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/me/accounts?fields=id,name,access_token,category,tasks&access_token=TOKEN&limit=10&after=AFTER"
+        );
+    }
+
+    #[test]
+    fn test_de_response_body_ok_json() {
+        let content = include_str!("../../tests/response_body_json_files/me__accounts.json");
+        let ok_json = serde_json::from_str::<AccountsResponseBodyOkJson>(content).unwrap();
+        assert_eq!(ok_json.data.len(), 2);
+        assert_eq!(ok_json.data[0].id, 109004958110000);
+        assert_eq!(ok_json.data[0].category.as_deref(), Some("Software"));
+        assert_eq!(
+            ok_json.data[0].tasks,
+            vec![
+                "ANALYZE",
+                "ADVERTISE",
+                "MODERATE",
+                "CREATE_CONTENT",
+                "MANAGE"
+            ]
+        );
+        assert!(ok_json.data[1].tasks.is_empty());
+        assert_eq!(
+            ok_json.paging.unwrap().next_cursor().as_deref(),
+            Some("QVFIUkFfAFTER")
+        );
+
+        let page = ok_json.data[0].to_managed_page(true).unwrap();
+        assert_eq!(page.access_token.inner(), "EAAPAGE1");
+        assert!(page.never_expires);
+    }
+}
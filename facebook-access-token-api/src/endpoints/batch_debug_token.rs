@@ -0,0 +1,270 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/batch-requests)
+//! [Ref](https://developers.facebook.com/docs/graph-api/reference/v15.0/debug_token)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use facebook_graph_api_object_error::Error;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, CONTENT_TYPE, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use url::{form_urlencoded, Url};
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        debug_token::DebugTokenResponseBodyOkJson,
+        URL_BASE, VERSION,
+    },
+    objects::{DebugTokenResult, ResponseBodyErrJson},
+};
+
+//
+// The Graph batch endpoint accepts at most 50 requests.
+pub const BATCH_DEBUG_TOKEN_INPUT_TOKENS_MAX: usize = 50;
+
+//
+#[derive(Clone)]
+pub struct BatchDebugTokenEndpoint {
+    pub input_tokens: Vec<Box<str>>,
+    pub access_token: Box<str>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for BatchDebugTokenEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BatchDebugTokenEndpoint")
+            .field(
+                "input_tokens",
+                &self
+                    .input_tokens
+                    .iter()
+                    .map(|x| redact(x))
+                    .collect::<Vec<_>>(),
+            )
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl BatchDebugTokenEndpoint {
+    pub fn new(
+        input_tokens: impl IntoIterator<Item = impl AsRef<str>>,
+        access_token: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            input_tokens: input_tokens
+                .into_iter()
+                .map(|x| x.as_ref().into())
+                .collect(),
+            access_token: access_token.as_ref().into(),
+            app_secret: None,
+            app_secret_time: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for BatchDebugTokenEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<BatchDebugTokenResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = Url::parse(URL_BASE).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        let version = self.version.as_deref().unwrap_or(VERSION);
+        let batch = self
+            .input_tokens
+            .iter()
+            .map(|input_token| {
+                let query = form_urlencoded::Serializer::new(String::new())
+                    .append_pair("input_token", input_token)
+                    .finish();
+                json!({
+                    "method": "GET",
+                    "relative_url": format!("{version}/debug_token?{query}"),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut body = form_urlencoded::Serializer::new(String::new());
+        body.append_pair("access_token", &self.access_token)
+            .append_pair("include_headers", "false")
+            .append_pair("batch", &serde_json::Value::from(batch).to_string());
+
+        if let Some(app_secret) = &self.app_secret {
+            body.extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(body.finish().into_bytes())
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+// One item per input_token, in request order. null means the sub request timed out.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BatchDebugTokenResponseBodyOkJson(pub Vec<Option<BatchDebugTokenResponseItem>>);
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BatchDebugTokenResponseItem {
+    pub code: u16,
+    pub body: String,
+}
+
+impl BatchDebugTokenResponseBodyOkJson {
+    // Returns len results, missing items are treated as timed out.
+    pub fn to_results(&self, len: usize) -> Vec<Result<DebugTokenResult, Error>> {
+        let mut results = Vec::with_capacity(len);
+        for i in 0..len {
+            let item = match self.0.get(i) {
+                Some(Some(item)) => item,
+                _ => {
+                    results.push(Err(Error::new_with_status_code_and_body(
+                        StatusCode::GATEWAY_TIMEOUT.as_u16(),
+                        "",
+                    )));
+                    continue;
+                }
+            };
+
+            if item.code == StatusCode::OK.as_u16() {
+                if let Ok(ok_json) =
+                    serde_json::from_str::<DebugTokenResponseBodyOkJson>(&item.body)
+                {
+                    results.push(Ok(ok_json.data));
+                    continue;
+                }
+            } else if let Ok(err_json) = serde_json::from_str::<ResponseBodyErrJson>(&item.body) {
+                results.push(Err(err_json.error));
+                continue;
+            }
+
+            results.push(Err(Error::new_with_status_code_and_body(
+                item.code, &item.body,
+            )));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = BatchDebugTokenEndpoint::new(["T1", "T&2"], "APP_ID|APP_SECRET", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::POST);
+        assert_eq!(req.uri(), "https://graph.facebook.com/");
+
+        let body = form_urlencoded::parse(req.body()).collect::<Vec<_>>();
+        assert_eq!(body[0], ("access_token".into(), "APP_ID|APP_SECRET".into()));
+        assert_eq!(body[1], ("include_headers".into(), "false".into()));
+        assert_eq!(body[2].0, "batch");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body[2].1).unwrap(),
+            json!([
+                {"method":"GET","relative_url":"v15.0/debug_token?input_token=T1"},
+                {"method":"GET","relative_url":"v15.0/debug_token?input_token=T%262"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let ok_body = include_str!(
+            "../../tests/response_body_json_files/debug_token__user_access_token_1.json"
+        );
+        let err_body = include_str!(
+            "../../tests/response_body_json_files/debug_token__400__debug_only_access_token.json"
+        );
+        let body = serde_json::to_vec(&json!([
+            {"code": 200, "body": ok_body},
+            {"code": 400, "body": err_body},
+            null,
+        ]))
+        .unwrap();
+
+        let ep = BatchDebugTokenEndpoint::new(["T1", "T2", "T3"], "APP_ID|APP_SECRET", None);
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(body)
+            .unwrap();
+        let ok_json = match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => ok_json,
+            x => panic!("{x:?}"),
+        };
+        assert_eq!(ok_json.0.len(), 3);
+
+        let mut results = ok_json.to_results(4).into_iter();
+        assert!(results.next().unwrap().is_ok());
+        let err = results.next().unwrap().unwrap_err();
+        assert_eq!(err.code, 190);
+        let err = results.next().unwrap().unwrap_err();
+        assert_eq!(err.as_status_code_and_body(), Some((504, "")));
+        let err = results.next().unwrap().unwrap_err();
+        assert_eq!(err.as_status_code_and_body(), Some((504, "")));
+    }
+}
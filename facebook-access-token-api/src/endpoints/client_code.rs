@@ -0,0 +1,179 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#get-a-long-lived-user-access-token-from-a-code)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+#[derive(Clone)]
+pub struct ClientCodeEndpoint {
+    pub app_id: u64,
+    pub app_secret: Box<str>,
+    pub app_secret_time: Option<u64>,
+    pub redirect_uri: Box<str>,
+    pub access_token: Box<str>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for ClientCodeEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClientCodeEndpoint")
+            .field("app_id", &self.app_id)
+            .field("app_secret", &redact(&self.app_secret))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("access_token", &redact(&self.access_token))
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl ClientCodeEndpoint {
+    pub fn new(
+        app_id: u64,
+        app_secret: impl AsRef<str>,
+        redirect_uri: impl AsRef<str>,
+        access_token: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            app_id,
+            app_secret: app_secret.as_ref().into(),
+            app_secret_time: None,
+            redirect_uri: redirect_uri.as_ref().into(),
+            access_token: access_token.as_ref().into(),
+            version: version.into(),
+        }
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for ClientCodeEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<ClientCodeResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/oauth/client_code",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.app_id.to_string());
+
+        url.query_pairs_mut()
+            .append_pair("client_secret", &self.app_secret);
+
+        url.query_pairs_mut()
+            .append_pair("redirect_uri", &self.redirect_uri);
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+        url.query_pairs_mut().extend_pairs(
+            AppSecretProof::with_optional_time(
+                &self.access_token,
+                &self.app_secret,
+                self.app_secret_time,
+            )
+            .pairs(),
+        );
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ClientCodeResponseBodyOkJson {
+    pub code: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let ep =
+            ClientCodeEndpoint::new(123, "APP_SECRET", "https://example.com/cb", "TOKEN", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/oauth/client_code?client_id=123&client_secret=APP_SECRET&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&access_token=TOKEN&appsecret_proof=84fc35d644eef7a4b9a5291e4b2d96fb1e08f7379b73bb0881ed562a182f6fd0"
+        );
+
+        let req = ep
+            .clone()
+            .app_secret_time(1672531200)
+            .render_request()
+            .unwrap();
+        assert_eq!(
+            req.uri().query().unwrap(),
+            "client_id=123&client_secret=APP_SECRET&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&access_token=TOKEN&appsecret_proof=85d9643cf0cdde3c2fab237cb356b58c261f21a1429fe5e37e56fcc4a4856777&appsecret_time=1672531200"
+        );
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(br#"{"code":"CLIENT_CODE"}"#.to_vec())
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => assert_eq!(ok_json.code, "CLIENT_CODE"),
+            x => panic!("{x:?}"),
+        }
+    }
+}
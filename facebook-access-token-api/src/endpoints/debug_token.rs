@@ -1,6 +1,9 @@
 //! [Ref](https://developers.facebook.com/docs/graph-api/reference/v15.0/debug_token)
 //! [Ref](https://developers.facebook.com/docs/facebook-login/guides/%20access-tokens/debugging)
 
+use facebook_access_token::{secret::redact, AppSecretProof};
+#[cfg(feature = "usage")]
+use facebook_graph_api_rate_limit::Usage;
 use http_api_client_endpoint::{
     http::{
         header::{ACCEPT, USER_AGENT},
@@ -20,14 +23,28 @@ use crate::{
 };
 
 //
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct DebugTokenEndpoint {
     pub input_token: Box<str>,
     pub access_token: Box<str>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
     //
     pub version: Option<Box<str>>,
 }
 
+impl core::fmt::Debug for DebugTokenEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DebugTokenEndpoint")
+            .field("input_token", &redact(&self.input_token))
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
 impl DebugTokenEndpoint {
     pub fn new(
         input_token: impl AsRef<str>,
@@ -37,9 +54,21 @@ impl DebugTokenEndpoint {
         Self {
             input_token: input_token.as_ref().into(),
             access_token: access_token.as_ref().into(),
+            app_secret: None,
+            app_secret_time: None,
             version: version.into(),
         }
     }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
 }
 
 impl Endpoint for DebugTokenEndpoint {
@@ -60,6 +89,17 @@ impl Endpoint for DebugTokenEndpoint {
             .append_pair("input_token", &self.input_token)
             .append_pair("access_token", &self.access_token);
 
+        if let Some(app_secret) = &self.app_secret {
+            url.query_pairs_mut().extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
+
         let request = Request::builder()
             .method(Method::GET)
             .uri(url.as_str())
@@ -76,13 +116,25 @@ impl Endpoint for DebugTokenEndpoint {
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
         let status = response.status();
+        #[cfg(feature = "usage")]
+        let usage = Usage::from_headers(response.headers());
         match status {
-            StatusCode::OK => Ok(EndpointRet::Ok(
-                serde_json::from_slice(response.body())
-                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
-            )),
+            StatusCode::OK => {
+                let ok_json: DebugTokenResponseBodyOkJson = serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?;
+                #[cfg(feature = "usage")]
+                let ok_json = DebugTokenResponseBodyOkJson { usage, ..ok_json };
+                Ok(EndpointRet::Ok(ok_json))
+            }
             status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
-                Ok(ok_json) => Ok(EndpointRet::Other((status, Ok(ok_json)))),
+                Ok(err_json) => {
+                    #[cfg(feature = "usage")]
+                    let err_json = ResponseBodyErrJson {
+                        usage: usage.into(),
+                        ..err_json
+                    };
+                    Ok(EndpointRet::Other((status, Ok(err_json))))
+                }
                 Err(_) => Ok(EndpointRet::Other((
                     status,
                     Err(response.body().to_owned()),
@@ -98,13 +150,17 @@ impl Endpoint for DebugTokenEndpoint {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DebugTokenResponseBodyOkJson {
     pub data: DebugTokenResult,
+    //
+    #[cfg(feature = "usage")]
+    #[serde(skip)]
+    pub usage: Usage,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::objects::debug_token::DebugTokenResultTypeExtra;
+    use crate::objects::debug_token::{DebugTokenResultExpires, DebugTokenResultTypeExtra};
 
     #[test]
     fn test_de_response_body_ok_json() {
@@ -125,6 +181,7 @@ mod tests {
                     }
                     DebugTokenResultTypeExtra::User(x) => panic!("{x:?}"),
                     DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                 }
             }
             Err(err) => panic!("{}", err),
@@ -149,6 +206,7 @@ mod tests {
                         assert!(info.metadata.is_some());
                     }
                     DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                 }
             }
             Err(err) => panic!("{}", err),
@@ -170,6 +228,7 @@ mod tests {
                         assert!(info.metadata.is_none());
                     }
                     DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                 }
             }
             Err(err) => panic!("{}", err),
@@ -190,6 +249,7 @@ mod tests {
                         assert_eq!(info.user_id, 123);
                     }
                     DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                 }
             }
             Err(err) => panic!("{}", err),
@@ -216,6 +276,7 @@ mod tests {
                         assert!(info.granular_scopes.is_some());
                     }
                     DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                 }
             }
             Err(err) => panic!("{}", err),
@@ -281,6 +342,7 @@ mod tests {
                 match ok_json.data.type_extra.unwrap() {
                     DebugTokenResultTypeExtra::App(x) => panic!("{x:?}"),
                     DebugTokenResultTypeExtra::User(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(x) => panic!("{x:?}"),
                     DebugTokenResultTypeExtra::Page(info) => {
                         assert_eq!(info.app_id, 257422819769992);
                         assert_eq!(info.user_id, 123);
@@ -331,5 +393,117 @@ mod tests {
             }
             Err(err) => panic!("{}", err),
         }
+
+        //
+        //
+        //
+        //
+        let content = include_str!(
+            "../../tests/response_body_json_files/debug_token__system_user_access_token.json"
+        );
+        match serde_json::from_str::<DebugTokenResponseBodyOkJson>(content) {
+            Ok(ok_json) => {
+                // println!("{ok_json:?}");
+                assert!(ok_json.data.is_valid);
+                match ok_json.data.type_extra.unwrap() {
+                    DebugTokenResultTypeExtra::App(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::User(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::Page(x) => panic!("{x:?}"),
+                    DebugTokenResultTypeExtra::SystemUser(info) => {
+                        assert_eq!(info.app_id, 257422819769992);
+                        assert_eq!(info.user_id, 100089123456789);
+                        assert!(matches!(info.expires(), DebugTokenResultExpires::Never));
+                    }
+                }
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_render_request() {
+        let ep = DebugTokenEndpoint::new("INPUT_TOKEN", "ACCESS_TOKEN", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/debug_token?input_token=INPUT_TOKEN&access_token=ACCESS_TOKEN"
+        );
+
+        let ep =
+            DebugTokenEndpoint::new("INPUT_TOKEN", "ACCESS_TOKEN", None).app_secret("APP_SECRET");
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/debug_token?input_token=INPUT_TOKEN&access_token=ACCESS_TOKEN&appsecret_proof=a727796e1b4e9053916f82f7a0b90f240862b289bb3c9ac5ff6e2231e18a491c"
+        );
+
+        let ep = DebugTokenEndpoint::new("INPUT_TOKEN", "ACCESS_TOKEN", None)
+            .app_secret("APP_SECRET")
+            .app_secret_time(1672531200);
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/debug_token?input_token=INPUT_TOKEN&access_token=ACCESS_TOKEN&appsecret_proof=61eb6bf9d00cce03ecea8b29edc32d8dec9d3d4a2608f357cc9734efd63fa581&appsecret_time=1672531200"
+        );
+    }
+
+    #[cfg(feature = "usage")]
+    #[test]
+    fn test_endpoint_parse_response_usage() {
+        let ep = DebugTokenEndpoint::new("INPUT_TOKEN", "ACCESS_TOKEN", None);
+        let usage_header = r#"{"call_count":95,"total_time":25,"total_cputime":25}"#;
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .header("x-app-usage", usage_header)
+            .body(
+                include_str!(
+                    "../../tests/response_body_json_files/debug_token__app_access_token.json"
+                )
+                .as_bytes()
+                .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => assert_eq!(ok_json.usage.max_pct(), Some(95)),
+            x => panic!("{x:?}"),
+        }
+
+        let res = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .header("x-app-usage", usage_header)
+            .body(
+                br#"{"error":{"message":"(#4) Application request limit reached","type":"OAuthException","code":4,"fbtrace_id":"x"}}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Other((_, Ok(err_json))) => {
+                assert_eq!(err_json.usage.max_pct(), Some(95))
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .header("x-app-usage", usage_header)
+            .body(b"<html></html>".to_vec())
+            .unwrap();
+        assert!(matches!(
+            ep.parse_response(res),
+            Err(EndpointError::DeResponseBodyOkJsonFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_endpoint_debug() {
+        let ep =
+            DebugTokenEndpoint::new("INPUT_TOKEN", "ACCESS_TOKEN", None).app_secret("APP_SECRET");
+        let debug = format!("{ep:?}");
+        assert!(debug.starts_with("DebugTokenEndpoint {"));
+        for secret in ["INPUT_TOKEN", "ACCESS_TOKEN", "APP_SECRET"] {
+            assert!(!debug.contains(secret), "{debug}");
+        }
     }
 }
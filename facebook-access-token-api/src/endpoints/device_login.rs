@@ -0,0 +1,176 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/for-devices)
+
+use facebook_access_token::secret::redact;
+use facebook_permission::FacebookPermission;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// access_token is a ClientAccessToken.
+//
+#[derive(Clone)]
+pub struct DeviceLoginEndpoint {
+    pub access_token: Box<str>,
+    pub scope: Option<Vec<FacebookPermission>>,
+    pub redirect_uri: Option<Box<str>>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for DeviceLoginEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeviceLoginEndpoint")
+            .field("access_token", &redact(&self.access_token))
+            .field("scope", &self.scope)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl DeviceLoginEndpoint {
+    pub fn new(access_token: impl AsRef<str>, version: impl Into<Option<Box<str>>>) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+            scope: None,
+            redirect_uri: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn scope(mut self, value: impl IntoIterator<Item = FacebookPermission>) -> Self {
+        self.scope = Some(value.into_iter().collect());
+        self
+    }
+
+    pub fn redirect_uri(mut self, value: impl AsRef<str>) -> Self {
+        self.redirect_uri = Some(value.as_ref().into());
+        self
+    }
+}
+
+impl Endpoint for DeviceLoginEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<DeviceLoginResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/device/login",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+
+        if let Some(scope) = &self.scope {
+            url.query_pairs_mut().append_pair(
+                "scope",
+                &scope
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+
+        if let Some(redirect_uri) = &self.redirect_uri {
+            url.query_pairs_mut()
+                .append_pair("redirect_uri", redirect_uri);
+        }
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeviceLoginResponseBodyOkJson {
+    pub code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    // seconds
+    pub expires_in: u64,
+    // seconds
+    pub interval: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let ep = DeviceLoginEndpoint::new("123|CLIENT_TOKEN", None)
+            .scope([FacebookPermission::PublicProfile, FacebookPermission::Email]);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::POST);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/device/login?access_token=123%7CCLIENT_TOKEN&scope=public_profile%2Cemail"
+        );
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(
+                br#"{"code":"CODE","user_code":"A1NWZ9","verification_uri":"https://www.facebook.com/device","expires_in":420,"interval":5}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => {
+                assert_eq!(ok_json.user_code, "A1NWZ9");
+                assert_eq!(ok_json.interval, 5);
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/for-devices#tech-step3)
+
+use facebook_access_token::secret::redact;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// access_token is a ClientAccessToken, code is from DeviceLoginEndpoint.
+//
+#[derive(Clone)]
+pub struct DeviceLoginStatusEndpoint {
+    pub access_token: Box<str>,
+    pub code: Box<str>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for DeviceLoginStatusEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeviceLoginStatusEndpoint")
+            .field("access_token", &redact(&self.access_token))
+            .field("code", &redact(&self.code))
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl DeviceLoginStatusEndpoint {
+    pub fn new(
+        access_token: impl AsRef<str>,
+        code: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+            code: code.as_ref().into(),
+            version: version.into(),
+        }
+    }
+}
+
+impl Endpoint for DeviceLoginStatusEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<DeviceLoginStatusResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/device/login_status",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token)
+            .append_pair("code", &self.code);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DeviceLoginStatusResponseBodyOkJson {
+    pub access_token: String,
+    pub expires_in: Option<usize>,
+    pub data_access_expiration_time: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = DeviceLoginStatusEndpoint::new("123|CLIENT_TOKEN", "CODE", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::POST);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/device/login_status?access_token=123%7CCLIENT_TOKEN&code=CODE"
+        );
+    }
+}
@@ -1,15 +1,34 @@
+#[cfg(feature = "batch")]
+use std::sync::Arc;
+
 use facebook_access_token::{
-    AccessTokenExpiresIn, AppAccessToken, LongLivedUserAccessToken, PageAccessToken,
-    PageSessionInfoAccessToken, ShortLivedUserAccessToken, UserAccessToken,
-    UserSessionInfoAccessToken,
+    AccessTokenExpiresIn, AccessTokenKind, AppAccessToken, ClientCode,
+    LongLivedInstagramUserAccessToken, LongLivedUserAccessToken, MachineId, PageAccessToken,
+    PageSessionInfoAccessToken, ShortLivedInstagramUserAccessToken, ShortLivedUserAccessToken,
+    SystemUserAccessToken, UserAccessToken, UserSessionInfoAccessToken,
 };
 use facebook_graph_api_object_error::Error;
+use facebook_permission::FacebookPermission;
+#[cfg(feature = "batch")]
+use futures_util::{stream, StreamExt as _};
 use http_api_client::{Client, ClientRespondEndpointError};
 use http_api_client_endpoint::http::StatusCode;
 
+#[cfg(feature = "batch")]
+use crate::endpoints::{
+    batch_debug_token::BATCH_DEBUG_TOKEN_INPUT_TOKENS_MAX, BatchDebugTokenEndpoint,
+};
+#[cfg(feature = "pkce")]
+use crate::login::PkceCodeVerifier;
 use crate::{
-    endpoints::{AccessTokenEndpoint, DebugTokenEndpoint, EndpointError, EndpointRet},
-    objects::{DebugTokenResult, ResponseBodyErrJson},
+    endpoints::{
+        AccessTokenEndpoint, AccountsEndpoint, ClientCodeEndpoint, DebugTokenEndpoint,
+        EndpointError, EndpointRet, InstagramAccessTokenEndpoint,
+        InstagramRefreshAccessTokenEndpoint, PermissionsEndpoint, RedeemClientCodeEndpoint,
+        RevokePermissionsEndpoint, RevokeSystemUserAccessTokenEndpoint,
+        SystemUserAccessTokensEndpoint,
+    },
+    objects::{DebugTokenResult, ManagedPage, Permission, ResponseBodyErrJson},
 };
 
 //
@@ -46,12 +65,178 @@ pub async fn get_long_lived_user_access_token<C: Client + Send + Sync>(
         EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
         EndpointRet::Other((status_code, Err(body))) => Ok(Err((
             status_code,
-            ResponseBodyErrJson {
-                error: Error::new_with_status_code_and_body(
-                    status_code.as_u16(),
-                    String::from_utf8_lossy(&body).as_ref(),
-                ),
-            },
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#get-a-long-lived-user-access-token-from-a-code
+// Run on the server, the code can be redeemed only once.
+//
+pub async fn gen_client_code<C: Client + Send + Sync>(
+    client: &C,
+    app_id: u64,
+    app_secret: impl AsRef<str>,
+    redirect_uri: impl AsRef<str>,
+    long_lived_user_access_token: impl Into<LongLivedUserAccessToken>,
+) -> Result<
+    Result<ClientCode, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = ClientCodeEndpoint::new(
+        app_id,
+        app_secret,
+        redirect_uri,
+        long_lived_user_access_token.into().inner(),
+        None,
+    );
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok(ok_json.code.into())),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// Run on the client, no app_secret is needed.
+// Pass the machine_id returned by a previous redeem, if any.
+//
+pub async fn redeem_client_code<C: Client + Send + Sync>(
+    client: &C,
+    app_id: u64,
+    redirect_uri: impl AsRef<str>,
+    client_code: impl Into<ClientCode>,
+    machine_id: Option<&MachineId>,
+) -> Result<
+    Result<
+        (
+            LongLivedUserAccessToken,
+            Option<AccessTokenExpiresIn>,
+            Option<MachineId>,
+        ),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let mut ep =
+        RedeemClientCodeEndpoint::new(app_id, redirect_uri, client_code.into().inner(), None);
+    if let Some(machine_id) = machine_id {
+        ep = ep.machine_id(machine_id.inner());
+    }
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+            ok_json.machine_id.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// https://developers.facebook.com/docs/facebook-login/guides/advanced/manual-flow#exchangecode
+// The redirect_uri must be the same as the one used in the login dialog.
+//
+pub async fn get_user_access_token_by_code<C: Client + Send + Sync>(
+    client: &C,
+    app_id: u64,
+    app_secret: impl AsRef<str>,
+    redirect_uri: impl AsRef<str>,
+    code: impl AsRef<str>,
+) -> Result<
+    Result<
+        (ShortLivedUserAccessToken, Option<AccessTokenExpiresIn>),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = AccessTokenEndpoint::new(
+        "authorization_code",
+        app_id,
+        Some(app_secret.as_ref().into()),
+        None,
+        None,
+    )
+    .redirect_uri(redirect_uri)
+    .code(code);
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// The PKCE variant of get_user_access_token_by_code, no app_secret is needed.
+//
+#[cfg(feature = "pkce")]
+pub async fn get_user_access_token_by_code_with_pkce<C: Client + Send + Sync>(
+    client: &C,
+    app_id: u64,
+    redirect_uri: impl AsRef<str>,
+    code: impl AsRef<str>,
+    code_verifier: &PkceCodeVerifier,
+) -> Result<
+    Result<
+        (ShortLivedUserAccessToken, Option<AccessTokenExpiresIn>),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = AccessTokenEndpoint::new("authorization_code", app_id, None, None, None)
+        .redirect_uri(redirect_uri)
+        .code(code)
+        .code_verifier(code_verifier.as_str());
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
         ))),
     }
 }
@@ -82,12 +267,10 @@ pub async fn gen_app_access_token<C: Client + Send + Sync>(
         EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
         EndpointRet::Other((status_code, Err(body))) => Ok(Err((
             status_code,
-            ResponseBodyErrJson {
-                error: Error::new_with_status_code_and_body(
-                    status_code.as_u16(),
-                    String::from_utf8_lossy(&body).as_ref(),
-                ),
-            },
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
         ))),
     }
 }
@@ -121,12 +304,10 @@ async fn gen_x_session_info_access_token_inner<C: Client + Send + Sync>(
         EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
         EndpointRet::Other((status_code, Err(body))) => Ok(Err((
             status_code,
-            ResponseBodyErrJson {
-                error: Error::new_with_status_code_and_body(
-                    status_code.as_u16(),
-                    String::from_utf8_lossy(&body).as_ref(),
-                ),
-            },
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
         ))),
     }
 }
@@ -197,12 +378,10 @@ async fn debug_x_access_token_inner<C: Client + Send + Sync>(
         EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
         EndpointRet::Other((status_code, Err(body))) => Ok(Err((
             status_code,
-            ResponseBodyErrJson {
-                error: Error::new_with_status_code_and_body(
-                    status_code.as_u16(),
-                    String::from_utf8_lossy(&body).as_ref(),
-                ),
-            },
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
         ))),
     }
 }
@@ -257,6 +436,27 @@ pub async fn debug_app_access_token<C: Client + Send + Sync>(
     debug_x_access_token_inner(client, token.inner(), token.inner()).await
 }
 
+//
+// Confirms a facebook_access_token::classify guess, Ok(None) when debug_token does not return the type, e.g. the token is invalid.
+// Instagram Login tokens can't be debugged with debug_token.
+//
+pub async fn confirm_access_token_kind_via_app_access_token<C: Client + Send + Sync>(
+    client: &C,
+    access_token: impl AsRef<str>,
+    app_access_token: impl Into<AppAccessToken>,
+) -> Result<
+    Result<Option<AccessTokenKind>, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let app_access_token = app_access_token.into();
+    match debug_x_access_token_inner(client, access_token.as_ref(), app_access_token.inner())
+        .await?
+    {
+        Ok(result) => Ok(Ok(result.kind())),
+        Err(err) => Ok(Err(err)),
+    }
+}
+
 //
 pub async fn debug_page_access_token<C: Client + Send + Sync>(
     client: &C,
@@ -269,6 +469,20 @@ pub async fn debug_page_access_token<C: Client + Send + Sync>(
     debug_x_access_token_inner(client, token.inner(), token.inner()).await
 }
 
+//
+pub async fn debug_page_access_token_via_app_access_token<C: Client + Send + Sync>(
+    client: &C,
+    page_access_token: impl Into<PageAccessToken>,
+    app_access_token: impl Into<AppAccessToken>,
+) -> Result<
+    Result<DebugTokenResult, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let input_token = page_access_token.into();
+    let access_token = app_access_token.into();
+    debug_x_access_token_inner(client, input_token.inner(), access_token.inner()).await
+}
+
 //
 pub async fn debug_user_session_info_access_token_via_app_access_token<C: Client + Send + Sync>(
     client: &C,
@@ -326,3 +540,696 @@ pub async fn debug_page_session_info_access_token_via_page_access_token<C: Clien
     let access_token = page_access_token.into();
     debug_x_access_token_inner(client, input_token.inner(), access_token.inner()).await
 }
+
+//
+// https://developers.facebook.com/docs/graph-api/batch-requests
+// Sends BATCH_DEBUG_TOKEN_INPUT_TOKENS_MAX input_tokens per request, at most max_in_flight requests at once.
+// The results keep the input order, a failed batch request fails every input_token of it.
+//
+#[cfg(feature = "batch")]
+pub async fn debug_access_tokens_in_batch_via_app_access_token<C: Client + Send + Sync>(
+    client: &C,
+    input_tokens: impl IntoIterator<Item = impl AsRef<str>>,
+    app_access_token: impl Into<AppAccessToken>,
+    max_in_flight: usize,
+) -> Vec<(
+    String,
+    Result<DebugTokenResult, BatchDebugTokenError<C::RespondError>>,
+)> {
+    let input_tokens = input_tokens
+        .into_iter()
+        .map(|x| x.as_ref().to_owned())
+        .collect::<Vec<_>>();
+    let access_token = app_access_token.into();
+
+    let chunks = stream::iter(input_tokens.chunks(BATCH_DEBUG_TOKEN_INPUT_TOKENS_MAX))
+        .map(|chunk| {
+            let ep = BatchDebugTokenEndpoint::new(chunk, access_token.inner(), None);
+            async move {
+                let results = match client.respond_endpoint(&ep).await {
+                    Ok(EndpointRet::Ok(ok_json)) => {
+                        let mut results = Vec::with_capacity(chunk.len());
+                        for ret in ok_json.to_results(chunk.len()) {
+                            results.push(match ret {
+                                Ok(result) => Ok(result),
+                                Err(error) => Err(BatchDebugTokenError::ResponseFailed(error)),
+                            });
+                        }
+                        results
+                    }
+                    Ok(EndpointRet::Other((status_code, ret))) => {
+                        let error = match ret {
+                            Ok(err_json) => err_json.error,
+                            Err(body) => Error::new_with_status_code_and_body(
+                                status_code.as_u16(),
+                                String::from_utf8_lossy(&body).as_ref(),
+                            ),
+                        };
+                        vec![Err(BatchDebugTokenError::ResponseFailed(error)); chunk.len()]
+                    }
+                    Err(err) => {
+                        vec![Err(BatchDebugTokenError::RespondFailed(Arc::new(err))); chunk.len()]
+                    }
+                };
+
+                chunk.iter().cloned().zip(results).collect::<Vec<_>>()
+            }
+        })
+        .buffered(max_in_flight.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    chunks.into_iter().flatten().collect()
+}
+
+//
+#[cfg(feature = "batch")]
+#[derive(Debug)]
+pub enum BatchDebugTokenError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    // The batch request of the input_token failed, shared by every input_token of it.
+    RespondFailed(Arc<ClientRespondEndpointError<RE, EndpointError, EndpointError>>),
+    ResponseFailed(Error),
+}
+
+#[cfg(feature = "batch")]
+impl<RE> Clone for BatchDebugTokenError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::RespondFailed(err) => Self::RespondFailed(err.clone()),
+            Self::ResponseFailed(err) => Self::ResponseFailed(err.clone()),
+        }
+    }
+}
+
+#[cfg(feature = "batch")]
+impl<RE> core::fmt::Display for BatchDebugTokenError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(feature = "batch")]
+impl<RE> std::error::Error for BatchDebugTokenError<RE> where
+    RE: std::error::Error + Send + Sync + 'static
+{
+}
+
+//
+// https://developers.facebook.com/docs/graph-api/reference/user/permissions/
+// Pass app_secret when the app requires appsecret_proof.
+//
+pub async fn get_user_permissions<C: Client + Send + Sync>(
+    client: &C,
+    user_access_token: impl Into<UserAccessToken>,
+    app_secret: Option<&str>,
+) -> Result<
+    Result<Vec<Permission>, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let mut ep = PermissionsEndpoint::new(user_access_token.into().inner(), None);
+    if let Some(app_secret) = app_secret {
+        ep = ep.app_secret(app_secret);
+    }
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok(ok_json.data)),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// Without permission, the app is deauthorized for the user.
+// Pass app_secret when the app requires appsecret_proof.
+//
+pub async fn revoke_user_permission<C: Client + Send + Sync>(
+    client: &C,
+    user_access_token: impl Into<UserAccessToken>,
+    permission: Option<FacebookPermission>,
+    app_secret: Option<&str>,
+) -> Result<
+    Result<bool, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let mut ep = RevokePermissionsEndpoint::new(user_access_token.into().inner(), None);
+    if let Some(app_secret) = app_secret {
+        ep = ep.app_secret(app_secret);
+    }
+    if let Some(permission) = permission {
+        ep = ep.permission(permission);
+    }
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok(ok_json.success)),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// https://developers.facebook.com/docs/pages/access-tokens#get-a-page-access-token
+// Follows all /me/accounts pages.
+// Pass app_secret when the app requires appsecret_proof.
+// The pages without an access_token, e.g. the user has no tasks on them, are skipped.
+//
+async fn get_page_access_tokens_inner<C: Client + Send + Sync>(
+    client: &C,
+    user_access_token: &str,
+    app_secret: Option<&str>,
+    never_expires: bool,
+) -> Result<
+    Result<Vec<ManagedPage>, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let mut pages = vec![];
+    let mut after = None;
+
+    loop {
+        let mut ep = AccountsEndpoint::new(user_access_token, None);
+        if let Some(app_secret) = app_secret {
+            ep = ep.app_secret(app_secret);
+        }
+        if let Some(after) = &after {
+            ep = ep.after(after);
+        }
+
+        let ret = client.respond_endpoint(&ep).await?;
+
+        match ret {
+            EndpointRet::Ok(ok_json) => {
+                pages.extend(
+                    ok_json
+                        .data
+                        .iter()
+                        .filter_map(|x| x.to_managed_page(never_expires)),
+                );
+
+                match ok_json.paging.and_then(|x| x.next_cursor()) {
+                    Some(next_cursor) => after = Some(next_cursor),
+                    None => break,
+                }
+            }
+            EndpointRet::Other((status_code, Ok(err_json))) => {
+                return Ok(Err((status_code, err_json)))
+            }
+            EndpointRet::Other((status_code, Err(body))) => {
+                return Ok(Err((
+                    status_code,
+                    ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                        status_code.as_u16(),
+                        String::from_utf8_lossy(&body).as_ref(),
+                    )),
+                )))
+            }
+        }
+    }
+
+    Ok(Ok(pages))
+}
+
+//
+pub async fn get_page_access_tokens<C: Client + Send + Sync>(
+    client: &C,
+    user_access_token: impl Into<UserAccessToken>,
+    app_secret: Option<&str>,
+) -> Result<
+    Result<Vec<ManagedPage>, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    get_page_access_tokens_inner(client, user_access_token.into().inner(), app_secret, false).await
+}
+
+//
+// The page access tokens never expire.
+//
+pub async fn get_page_access_tokens_via_long_lived_user_access_token<C: Client + Send + Sync>(
+    client: &C,
+    long_lived_user_access_token: impl Into<LongLivedUserAccessToken>,
+    app_secret: Option<&str>,
+) -> Result<
+    Result<Vec<ManagedPage>, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    get_page_access_tokens_inner(
+        client,
+        long_lived_user_access_token.into().inner(),
+        app_secret,
+        true,
+    )
+    .await
+}
+
+//
+// https://developers.facebook.com/docs/marketing-api/system-users/install-apps-and-generate-tokens
+//
+pub async fn gen_system_user_access_token<C: Client + Send + Sync>(
+    client: &C,
+    business_id: u64,
+    app_id: u64,
+    app_secret: impl AsRef<str>,
+    admin_system_user_access_token: impl Into<SystemUserAccessToken>,
+    scope: impl IntoIterator<Item = FacebookPermission>,
+    system_user_id: Option<u64>,
+) -> Result<
+    Result<
+        (SystemUserAccessToken, Option<AccessTokenExpiresIn>),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let mut ep = SystemUserAccessTokensEndpoint::new(
+        business_id,
+        app_id,
+        scope,
+        admin_system_user_access_token.into().inner(),
+        app_secret,
+        None,
+    );
+    if let Some(system_user_id) = system_user_id {
+        ep = ep.system_user_id(system_user_id);
+    }
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+pub async fn revoke_system_user_access_token<C: Client + Send + Sync>(
+    client: &C,
+    app_id: u64,
+    app_secret: impl AsRef<str>,
+    system_user_access_token: impl Into<SystemUserAccessToken>,
+    admin_system_user_access_token: impl Into<SystemUserAccessToken>,
+) -> Result<
+    Result<bool, (StatusCode, ResponseBodyErrJson)>,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = RevokeSystemUserAccessTokenEndpoint::new(
+        app_id,
+        app_secret,
+        system_user_access_token.into().inner(),
+        admin_system_user_access_token.into().inner(),
+        None,
+    );
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok(ok_json.success)),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+// https://developers.facebook.com/docs/instagram-basic-display-api/guides/long-lived-access-tokens
+//
+pub async fn get_long_lived_instagram_user_access_token<C: Client + Send + Sync>(
+    client: &C,
+    app_secret: impl AsRef<str>,
+    short_lived_instagram_user_access_token: impl Into<ShortLivedInstagramUserAccessToken>,
+) -> Result<
+    Result<
+        (
+            LongLivedInstagramUserAccessToken,
+            Option<AccessTokenExpiresIn>,
+        ),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = InstagramAccessTokenEndpoint::new(
+        app_secret,
+        short_lived_instagram_user_access_token.into().inner(),
+        None,
+    );
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+//
+pub async fn refresh_long_lived_instagram_user_access_token<C: Client + Send + Sync>(
+    client: &C,
+    long_lived_instagram_user_access_token: impl Into<LongLivedInstagramUserAccessToken>,
+) -> Result<
+    Result<
+        (
+            LongLivedInstagramUserAccessToken,
+            Option<AccessTokenExpiresIn>,
+        ),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    ClientRespondEndpointError<C::RespondError, EndpointError, EndpointError>,
+> {
+    let ep = InstagramRefreshAccessTokenEndpoint::new(
+        long_lived_instagram_user_access_token.into().inner(),
+        None,
+    );
+
+    let ret = client.respond_endpoint(&ep).await?;
+
+    match ret {
+        EndpointRet::Ok(ok_json) => Ok(Ok((
+            ok_json.access_token.into(),
+            ok_json.expires_in.map(Into::into),
+        ))),
+        EndpointRet::Other((status_code, Ok(err_json))) => Ok(Err((status_code, err_json))),
+        EndpointRet::Other((status_code, Err(body))) => Ok(Err((
+            status_code,
+            ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                status_code.as_u16(),
+                String::from_utf8_lossy(&body).as_ref(),
+            )),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Error as IoError;
+    #[cfg(feature = "batch")]
+    use std::io::ErrorKind as IoErrorKind;
+
+    use facebook_access_token::AppSecretProof;
+    use futures_lite::future::block_on;
+    #[cfg(feature = "batch")]
+    use futures_lite::future::yield_now;
+    use http_api_client::{async_trait, Body, Request, Response};
+    #[cfg(feature = "batch")]
+    use serde_json::{json, Value};
+    #[cfg(feature = "batch")]
+    use url::form_urlencoded;
+
+    #[cfg(feature = "batch")]
+    fn batch_input_tokens(request: &Request<Body>) -> Vec<String> {
+        let batch = form_urlencoded::parse(request.body())
+            .find(|(k, _)| k == "batch")
+            .map(|(_, v)| serde_json::from_str::<Vec<Value>>(&v).unwrap())
+            .unwrap();
+        batch
+            .iter()
+            .map(|x| {
+                let relative_url = x["relative_url"].as_str().unwrap();
+                relative_url
+                    .split_once("input_token=")
+                    .unwrap()
+                    .1
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    // Input tokens are T<user_id>. The batch that contains failing_input_token fails,
+    // earlier batches are delayed more so that they finish last.
+    #[cfg(feature = "batch")]
+    struct BatchMockClient {
+        failing_input_token: &'static str,
+    }
+
+    #[cfg(feature = "batch")]
+    #[async_trait]
+    impl Client for BatchMockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let input_tokens = batch_input_tokens(&request);
+
+            let first = input_tokens[0][1..].parse::<usize>().unwrap();
+            for _ in 0..(1000 - first) {
+                yield_now().await;
+            }
+
+            if input_tokens.iter().any(|x| x == self.failing_input_token) {
+                return Err(IoError::new(IoErrorKind::ConnectionReset, "reset"));
+            }
+
+            let items = input_tokens
+                .iter()
+                .map(|x| {
+                    let body = json!({"data": {
+                        "app_id": "1",
+                        "type": "USER",
+                        "application": "x",
+                        "data_access_expires_at": 0,
+                        "expires_at": 0,
+                        "is_valid": true,
+                        "scopes": [],
+                        "user_id": &x[1..],
+                    }});
+                    json!({"code": 200, "body": body.to_string()})
+                })
+                .collect::<Vec<_>>();
+            Ok(Response::builder()
+                .status(200)
+                .body(serde_json::to_vec(&items).unwrap())
+                .unwrap())
+        }
+    }
+
+    #[cfg(feature = "batch")]
+    #[test]
+    fn test_debug_access_tokens_in_batch_via_app_access_token() {
+        block_on(async {
+            let input_tokens = (0..120).map(|i| format!("T{i}")).collect::<Vec<_>>();
+
+            let rets = debug_access_tokens_in_batch_via_app_access_token(
+                &BatchMockClient {
+                    failing_input_token: "T60",
+                },
+                &input_tokens,
+                AppAccessToken::with_app_secret(1, "APP_SECRET"),
+                3,
+            )
+            .await;
+
+            assert_eq!(rets.len(), 120);
+            for (i, (input_token, ret)) in rets.into_iter().enumerate() {
+                assert_eq!(input_token, format!("T{i}"));
+                match ret {
+                    Ok(result) if !(50..100).contains(&i) => {
+                        assert_eq!(
+                            result.to_access_token_with_metadata(()).user_id,
+                            Some(i as u64)
+                        )
+                    }
+                    Err(BatchDebugTokenError::RespondFailed(err)) if (50..100).contains(&i) => {
+                        assert!(matches!(
+                            err.as_ref(),
+                            ClientRespondEndpointError::RespondFailed(_)
+                        ))
+                    }
+                    x => panic!("{i} {x:?}"),
+                }
+            }
+        })
+    }
+
+    struct AccountsMockClient;
+
+    #[async_trait]
+    impl Client for AccountsMockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let query = request.uri().query().unwrap();
+            assert!(query.contains(&format!(
+                "appsecret_proof={}",
+                AppSecretProof::new("EAA", "APP_SECRET").proof
+            )));
+
+            let body = if query.contains("after=QVFIUkFfAFTER") {
+                r#"{"data":[{"id":"109004958110002","name":"Baz","access_token":"EAAPAGE3"},{"id":"109004958110003","name":"Qux","tasks":[]}],"paging":{"cursors":{"before":"QVFIUkFfBEFORE2","after":"QVFIUkFfAFTER2"}}}"#
+            } else {
+                include_str!("../../tests/response_body_json_files/me__accounts.json")
+            };
+            Ok(Response::builder()
+                .status(200)
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_get_page_access_tokens_via_long_lived_user_access_token() {
+        block_on(async {
+            let pages = get_page_access_tokens_via_long_lived_user_access_token(
+                &AccountsMockClient,
+                "EAA",
+                Some("APP_SECRET"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(
+                pages.iter().map(|x| x.id).collect::<Vec<_>>(),
+                vec![109004958110000, 109004958110001, 109004958110002]
+            );
+            assert_eq!(pages[2].access_token.inner(), "EAAPAGE3");
+            assert!(pages.iter().all(|x| x.never_expires));
+        })
+    }
+
+    struct PermissionsMockClient;
+
+    #[async_trait]
+    impl Client for PermissionsMockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let query = request.uri().query().unwrap();
+            assert!(query.contains(&format!(
+                "appsecret_proof={}",
+                AppSecretProof::new("EAA", "APP_SECRET").proof
+            )));
+
+            let body = match request.method().as_str() {
+                "GET" => r#"{"data":[{"permission":"public_profile","status":"granted"}]}"#,
+                "DELETE" => r#"{"success":true}"#,
+                method => panic!("unexpected method {method}"),
+            };
+            Ok(Response::builder()
+                .status(200)
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_permissions_with_app_secret() {
+        block_on(async {
+            let permissions =
+                get_user_permissions(&PermissionsMockClient, "EAA", Some("APP_SECRET"))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(permissions.len(), 1);
+
+            assert!(revoke_user_permission(
+                &PermissionsMockClient,
+                "EAA",
+                Some(FacebookPermission::Email),
+                Some("APP_SECRET"),
+            )
+            .await
+            .unwrap()
+            .unwrap());
+        })
+    }
+
+    #[cfg(feature = "usage")]
+    struct UsageMockClient;
+
+    #[cfg(feature = "usage")]
+    #[async_trait]
+    impl Client for UsageMockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            _request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            Ok(Response::builder()
+                .status(400)
+                .header(
+                    "x-app-usage",
+                    r#"{"call_count":100,"total_time":25,"total_cputime":25}"#,
+                )
+                .body(
+                    br#"{"error":{"message":"(#4) Application request limit reached","type":"OAuthException","code":4,"fbtrace_id":"x"}}"#
+                        .to_vec(),
+                )
+                .unwrap())
+        }
+    }
+
+    #[cfg(feature = "usage")]
+    #[test]
+    fn test_err_json_keeps_usage() {
+        block_on(async {
+            let (_, err_json) = debug_app_access_token(&UsageMockClient, "1|x")
+                .await
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(err_json.error.code, 4);
+            assert_eq!(err_json.usage.max_pct(), Some(100));
+
+            let (_, err_json) = gen_app_access_token(&UsageMockClient, 1, "APP_SECRET")
+                .await
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(err_json.usage.max_pct(), Some(100));
+        })
+    }
+}
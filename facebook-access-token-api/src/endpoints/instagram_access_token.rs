@@ -0,0 +1,155 @@
+//! [Ref](https://developers.facebook.com/docs/instagram-basic-display-api/reference/access_token)
+
+use facebook_access_token::secret::redact;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        access_token::AccessTokenResponseBodyOkJson,
+        common::{EndpointError, EndpointRet},
+        INSTAGRAM_URL_BASE,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// Exchanges a ShortLivedInstagramUserAccessToken for a LongLivedInstagramUserAccessToken.
+//
+#[derive(Clone)]
+pub struct InstagramAccessTokenEndpoint {
+    pub app_secret: Box<str>,
+    pub access_token: Box<str>,
+    //
+    pub url_base: Option<Box<str>>,
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for InstagramAccessTokenEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InstagramAccessTokenEndpoint")
+            .field("app_secret", &redact(&self.app_secret))
+            .field("access_token", &redact(&self.access_token))
+            .field("url_base", &self.url_base)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl InstagramAccessTokenEndpoint {
+    pub fn new(
+        app_secret: impl AsRef<str>,
+        access_token: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            app_secret: app_secret.as_ref().into(),
+            access_token: access_token.as_ref().into(),
+            url_base: None,
+            version: version.into(),
+        }
+    }
+
+    // Default is INSTAGRAM_URL_BASE.
+    pub fn url_base(mut self, value: impl AsRef<str>) -> Self {
+        self.url_base = Some(value.as_ref().into());
+        self
+    }
+}
+
+impl Endpoint for InstagramAccessTokenEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<AccessTokenResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url_base = self.url_base.as_deref().unwrap_or(INSTAGRAM_URL_BASE);
+        let url = match &self.version {
+            Some(version) => format!("{url_base}/{version}/access_token"),
+            None => format!("{url_base}/access_token"),
+        };
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("grant_type", "ig_exchange_token")
+            .append_pair("client_secret", &self.app_secret)
+            .append_pair("access_token", &self.access_token);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let ep = InstagramAccessTokenEndpoint::new("APP_SECRET", "IGQV_SHORT", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri(),
+            "https://graph.instagram.com/access_token?grant_type=ig_exchange_token&client_secret=APP_SECRET&access_token=IGQV_SHORT"
+        );
+
+        let ep =
+            InstagramAccessTokenEndpoint::new("APP_SECRET", "IGQV_SHORT", Some("v15.0".into()))
+                .url_base("http://127.0.0.1:8080");
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri(),
+            "http://127.0.0.1:8080/v15.0/access_token?grant_type=ig_exchange_token&client_secret=APP_SECRET&access_token=IGQV_SHORT"
+        );
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(
+                br#"{"access_token":"IGQV_LONG","token_type":"bearer","expires_in":5183944}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => {
+                assert_eq!(ok_json.access_token, "IGQV_LONG");
+                assert_eq!(ok_json.expires_in, Some(5183944));
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+}
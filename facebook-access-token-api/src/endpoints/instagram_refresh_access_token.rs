@@ -0,0 +1,123 @@
+//! [Ref](https://developers.facebook.com/docs/instagram-basic-display-api/reference/refresh_access_token)
+
+use facebook_access_token::secret::redact;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        access_token::AccessTokenResponseBodyOkJson,
+        common::{EndpointError, EndpointRet},
+        INSTAGRAM_URL_BASE,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// The LongLivedInstagramUserAccessToken must be at least 24 hours old and not expired.
+//
+#[derive(Clone)]
+pub struct InstagramRefreshAccessTokenEndpoint {
+    pub access_token: Box<str>,
+    //
+    pub url_base: Option<Box<str>>,
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for InstagramRefreshAccessTokenEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InstagramRefreshAccessTokenEndpoint")
+            .field("access_token", &redact(&self.access_token))
+            .field("url_base", &self.url_base)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl InstagramRefreshAccessTokenEndpoint {
+    pub fn new(access_token: impl AsRef<str>, version: impl Into<Option<Box<str>>>) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+            url_base: None,
+            version: version.into(),
+        }
+    }
+
+    // Default is INSTAGRAM_URL_BASE.
+    pub fn url_base(mut self, value: impl AsRef<str>) -> Self {
+        self.url_base = Some(value.as_ref().into());
+        self
+    }
+}
+
+impl Endpoint for InstagramRefreshAccessTokenEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<AccessTokenResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url_base = self.url_base.as_deref().unwrap_or(INSTAGRAM_URL_BASE);
+        let url = match &self.version {
+            Some(version) => format!("{url_base}/{version}/refresh_access_token"),
+            None => format!("{url_base}/refresh_access_token"),
+        };
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("grant_type", "ig_refresh_token")
+            .append_pair("access_token", &self.access_token);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = InstagramRefreshAccessTokenEndpoint::new("IGQV_LONG", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri(),
+            "https://graph.instagram.com/refresh_access_token?grant_type=ig_refresh_token&access_token=IGQV_LONG"
+        );
+    }
+}
@@ -1,9 +1,37 @@
 //
 pub mod access_token;
+pub mod accounts;
+pub mod batch_debug_token;
+pub mod client_code;
 pub mod debug_token;
+pub mod device_login;
+pub mod device_login_status;
+pub mod instagram_access_token;
+pub mod instagram_refresh_access_token;
+pub mod oidc_jwks;
+pub mod permissions;
+pub mod redeem_client_code;
+pub mod revoke_permissions;
+pub mod revoke_system_user_access_token;
+pub mod system_user_access_tokens;
+pub mod system_users;
 
 pub use access_token::AccessTokenEndpoint;
+pub use accounts::AccountsEndpoint;
+pub use batch_debug_token::BatchDebugTokenEndpoint;
+pub use client_code::ClientCodeEndpoint;
 pub use debug_token::DebugTokenEndpoint;
+pub use device_login::DeviceLoginEndpoint;
+pub use device_login_status::DeviceLoginStatusEndpoint;
+pub use instagram_access_token::InstagramAccessTokenEndpoint;
+pub use instagram_refresh_access_token::InstagramRefreshAccessTokenEndpoint;
+pub use oidc_jwks::OidcJwksEndpoint;
+pub use permissions::PermissionsEndpoint;
+pub use redeem_client_code::RedeemClientCodeEndpoint;
+pub use revoke_permissions::RevokePermissionsEndpoint;
+pub use revoke_system_user_access_token::RevokeSystemUserAccessTokenEndpoint;
+pub use system_user_access_tokens::SystemUserAccessTokensEndpoint;
+pub use system_users::SystemUsersEndpoint;
 
 //
 pub mod common;
@@ -17,3 +45,5 @@ pub use helper::*;
 //
 pub const URL_BASE: &str = "https://graph.facebook.com";
 pub const VERSION: &str = "v15.0";
+
+pub const INSTAGRAM_URL_BASE: &str = "https://graph.instagram.com";
@@ -0,0 +1,90 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/limited-login/token/validating)
+
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use url::Url;
+
+use crate::{
+    endpoints::common::{EndpointError, EndpointRet},
+    objects::{Jwks, ResponseBodyErrJson},
+};
+
+//
+pub const OIDC_JWKS_URL: &str = "https://limited.facebook.com/.well-known/oauth/openid/jwks/";
+
+//
+#[derive(Debug, Clone, Default)]
+pub struct OidcJwksEndpoint {
+    pub url: Option<Box<str>>,
+}
+
+impl OidcJwksEndpoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn url(mut self, value: impl AsRef<str>) -> Self {
+        self.url = Some(value.as_ref().into());
+        self
+    }
+}
+
+impl Endpoint for OidcJwksEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<Jwks>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = Url::parse(self.url.as_deref().unwrap_or(OIDC_JWKS_URL))
+            .map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_render_request() {
+        let ep = OidcJwksEndpoint::new();
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(req.uri(), OIDC_JWKS_URL);
+    }
+}
@@ -0,0 +1,187 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#redeem-code)
+
+use facebook_access_token::secret::redact;
+#[cfg(feature = "usage")]
+use facebook_graph_api_rate_limit::Usage;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// Redeeming a client_code does not take a grant_type nor the app_secret.
+//
+#[derive(Clone)]
+pub struct RedeemClientCodeEndpoint {
+    pub app_id: u64,
+    pub redirect_uri: Box<str>,
+    pub client_code: Box<str>,
+    pub machine_id: Option<Box<str>>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for RedeemClientCodeEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RedeemClientCodeEndpoint")
+            .field("app_id", &self.app_id)
+            .field("redirect_uri", &self.redirect_uri)
+            .field("client_code", &redact(&self.client_code))
+            .field("machine_id", &self.machine_id.as_deref().map(redact))
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl RedeemClientCodeEndpoint {
+    pub fn new(
+        app_id: u64,
+        redirect_uri: impl AsRef<str>,
+        client_code: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            app_id,
+            redirect_uri: redirect_uri.as_ref().into(),
+            client_code: client_code.as_ref().into(),
+            machine_id: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn machine_id(mut self, value: impl AsRef<str>) -> Self {
+        self.machine_id = Some(value.as_ref().into());
+        self
+    }
+}
+
+impl Endpoint for RedeemClientCodeEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<RedeemClientCodeResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/oauth/access_token",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.app_id.to_string());
+
+        url.query_pairs_mut()
+            .append_pair("redirect_uri", &self.redirect_uri);
+
+        url.query_pairs_mut().append_pair("code", &self.client_code);
+
+        if let Some(machine_id) = &self.machine_id {
+            url.query_pairs_mut().append_pair("machine_id", machine_id);
+        }
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        #[cfg(feature = "usage")]
+        let usage = Usage::from_headers(response.headers());
+        match status {
+            StatusCode::OK => {
+                let ok_json: RedeemClientCodeResponseBodyOkJson =
+                    serde_json::from_slice(response.body())
+                        .map_err(EndpointError::DeResponseBodyOkJsonFailed)?;
+                #[cfg(feature = "usage")]
+                let ok_json = RedeemClientCodeResponseBodyOkJson { usage, ..ok_json };
+                Ok(EndpointRet::Ok(ok_json))
+            }
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => {
+                    #[cfg(feature = "usage")]
+                    let err_json = ResponseBodyErrJson {
+                        usage: usage.into(),
+                        ..err_json
+                    };
+                    Ok(EndpointRet::Other((status, Ok(err_json))))
+                }
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RedeemClientCodeResponseBodyOkJson {
+    pub access_token: String,
+    pub expires_in: Option<usize>,
+    pub machine_id: Option<String>,
+    //
+    #[cfg(feature = "usage")]
+    #[serde(skip)]
+    pub usage: Usage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let ep = RedeemClientCodeEndpoint::new(123, "https://example.com/cb", "CLIENT_CODE", None)
+            .machine_id("MACHINE_ID");
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/oauth/access_token?client_id=123&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&code=CLIENT_CODE&machine_id=MACHINE_ID"
+        );
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(
+                br#"{"access_token":"TOKEN","machine_id":"MACHINE_ID","expires_in":5183944}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => {
+                assert_eq!(ok_json.access_token, "TOKEN");
+                assert_eq!(ok_json.expires_in, Some(5183944));
+                assert_eq!(ok_json.machine_id.as_deref(), Some("MACHINE_ID"));
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/reference/user/permissions/#Deleting)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use facebook_permission::FacebookPermission;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// Without permission, all permissions are revoked and the app is deauthorized.
+//
+#[derive(Clone)]
+pub struct RevokePermissionsEndpoint {
+    pub access_token: Box<str>,
+    pub user_id: Option<u64>,
+    pub permission: Option<FacebookPermission>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for RevokePermissionsEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RevokePermissionsEndpoint")
+            .field("access_token", &redact(&self.access_token))
+            .field("user_id", &self.user_id)
+            .field("permission", &self.permission)
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl RevokePermissionsEndpoint {
+    pub fn new(access_token: impl AsRef<str>, version: impl Into<Option<Box<str>>>) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+            user_id: None,
+            permission: None,
+            app_secret: None,
+            app_secret_time: None,
+            version: version.into(),
+        }
+    }
+
+    // Default is me.
+    pub fn user_id(mut self, value: u64) -> Self {
+        self.user_id = Some(value);
+        self
+    }
+
+    pub fn permission(mut self, value: FacebookPermission) -> Self {
+        self.permission = Some(value);
+        self
+    }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for RevokePermissionsEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<RevokePermissionsResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let mut url = format!(
+            "{}/{}/{}/permissions",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+            self.user_id
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| "me".into()),
+        );
+        if let Some(permission) = &self.permission {
+            url = format!("{url}/{permission}");
+        }
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+
+        if let Some(app_secret) = &self.app_secret {
+            url.query_pairs_mut().extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RevokePermissionsResponseBodyOkJson {
+    pub success: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = RevokePermissionsEndpoint::new("TOKEN", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::DELETE);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/me/permissions?access_token=TOKEN"
+        );
+
+        let ep = RevokePermissionsEndpoint::new("ACCESS_TOKEN", None)
+            .app_secret("APP_SECRET")
+            .app_secret_time(1672531200);
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/me/permissions?access_token=ACCESS_TOKEN&appsecret_proof=61eb6bf9d00cce03ecea8b29edc32d8dec9d3d4a2608f357cc9734efd63fa581&appsecret_time=1672531200"
+        );
+
+        let ep = RevokePermissionsEndpoint::new("TOKEN", None)
+            .user_id(123)
+            .permission(FacebookPermission::PagesShowList);
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/123/permissions/pages_show_list?access_token=TOKEN"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! [Ref](https://developers.facebook.com/docs/marketing-api/system-users/install-apps-and-generate-tokens)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use facebook_permission::FacebookPermission;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+// access_token is an admin SystemUserAccessToken of the business, appsecret_proof is required.
+// Without system_user_id, the token is generated for the owner of access_token.
+//
+#[derive(Clone)]
+pub struct SystemUserAccessTokensEndpoint {
+    pub business_id: u64,
+    pub app_id: u64,
+    pub scope: Vec<FacebookPermission>,
+    pub system_user_id: Option<u64>,
+    pub set_token_expires_in_60_days: Option<bool>,
+    //
+    pub access_token: Box<str>,
+    pub app_secret: Box<str>,
+    pub app_secret_time: Option<u64>,
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for SystemUserAccessTokensEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SystemUserAccessTokensEndpoint")
+            .field("business_id", &self.business_id)
+            .field("app_id", &self.app_id)
+            .field("scope", &self.scope)
+            .field("system_user_id", &self.system_user_id)
+            .field(
+                "set_token_expires_in_60_days",
+                &self.set_token_expires_in_60_days,
+            )
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &redact(&self.app_secret))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl SystemUserAccessTokensEndpoint {
+    pub fn new(
+        business_id: u64,
+        app_id: u64,
+        scope: impl IntoIterator<Item = FacebookPermission>,
+        access_token: impl AsRef<str>,
+        app_secret: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            business_id,
+            app_id,
+            scope: scope.into_iter().collect(),
+            system_user_id: None,
+            set_token_expires_in_60_days: None,
+            access_token: access_token.as_ref().into(),
+            app_secret: app_secret.as_ref().into(),
+            app_secret_time: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn system_user_id(mut self, value: u64) -> Self {
+        self.system_user_id = Some(value);
+        self
+    }
+
+    pub fn set_token_expires_in_60_days(mut self, value: bool) -> Self {
+        self.set_token_expires_in_60_days = Some(value);
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for SystemUserAccessTokensEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<SystemUserAccessTokensResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/{}/system_user_access_tokens",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+            self.business_id,
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("business_app", &self.app_id.to_string());
+
+        url.query_pairs_mut().append_pair(
+            "scope",
+            &self
+                .scope
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+
+        if let Some(system_user_id) = self.system_user_id {
+            url.query_pairs_mut()
+                .append_pair("system_user_id", &system_user_id.to_string());
+        }
+
+        if let Some(set_token_expires_in_60_days) = self.set_token_expires_in_60_days {
+            url.query_pairs_mut().append_pair(
+                "set_token_expires_in_60_days",
+                &set_token_expires_in_60_days.to_string(),
+            );
+        }
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+        url.query_pairs_mut().extend_pairs(
+            AppSecretProof::with_optional_time(
+                &self.access_token,
+                &self.app_secret,
+                self.app_secret_time,
+            )
+            .pairs(),
+        );
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SystemUserAccessTokensResponseBodyOkJson {
+    pub access_token: String,
+    pub expires_in: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let ep = SystemUserAccessTokensEndpoint::new(
+            1234567890,
+            123,
+            [
+                FacebookPermission::BusinessManagement,
+                FacebookPermission::PagesShowList,
+            ],
+            "TOKEN",
+            "APP_SECRET",
+            None,
+        )
+        .system_user_id(100089123456789)
+        .set_token_expires_in_60_days(true);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::POST);
+        assert_eq!(
+            req.uri().path_and_query().unwrap().as_str(),
+            format!("/v15.0/1234567890/system_user_access_tokens?business_app=123&scope=business_management%2Cpages_show_list&system_user_id=100089123456789&set_token_expires_in_60_days=true&access_token=TOKEN&appsecret_proof={}", AppSecretProof::new("TOKEN", "APP_SECRET").proof)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! [Ref](https://developers.facebook.com/docs/marketing-api/reference/business/system_users/)
+
+use facebook_access_token::{secret::redact, AppSecretProof};
+use facebook_graph_api_object_paging::cursor_based_pagination::Paging;
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, USER_AGENT},
+        Method, StatusCode,
+    },
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    endpoints::{
+        common::{EndpointError, EndpointRet},
+        URL_BASE, VERSION,
+    },
+    objects::{ResponseBodyErrJson, SystemUser},
+};
+
+//
+#[derive(Clone)]
+pub struct SystemUsersEndpoint {
+    pub business_id: u64,
+    pub limit: Option<usize>,
+    pub after: Option<Box<str>>,
+    //
+    pub access_token: Box<str>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
+    pub version: Option<Box<str>>,
+}
+
+impl core::fmt::Debug for SystemUsersEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SystemUsersEndpoint")
+            .field("business_id", &self.business_id)
+            .field("limit", &self.limit)
+            .field("after", &self.after)
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl SystemUsersEndpoint {
+    pub fn new(
+        business_id: u64,
+        access_token: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            business_id,
+            limit: None,
+            after: None,
+            access_token: access_token.as_ref().into(),
+            app_secret: None,
+            app_secret_time: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn limit(mut self, value: usize) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    pub fn after(mut self, value: impl AsRef<str>) -> Self {
+        self.after = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
+}
+
+impl Endpoint for SystemUsersEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = EndpointRet<SystemUsersResponseBodyOkJson>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let url = format!(
+            "{}/{}/{}/system_users?fields={}",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+            self.business_id,
+            SystemUser::fields(),
+        );
+        let mut url = Url::parse(&url).map_err(EndpointError::MakeRequestUrlFailed)?;
+
+        url.query_pairs_mut()
+            .append_pair("access_token", &self.access_token);
+        if let Some(app_secret) = &self.app_secret {
+            url.query_pairs_mut().extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
+        if let Some(limit) = self.limit {
+            url.query_pairs_mut()
+                .append_pair("limit", limit.to_string().as_str());
+        }
+        if let Some(after) = &self.after {
+            url.query_pairs_mut().append_pair("after", after);
+        }
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .header(USER_AGENT, "facebook-access-token-api")
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        match status {
+            StatusCode::OK => Ok(EndpointRet::Ok(
+                serde_json::from_slice(response.body())
+                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
+            )),
+            status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
+                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Err(_) => Ok(EndpointRet::Other((
+                    status,
+                    Err(response.body().to_owned()),
+                ))),
+            },
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SystemUsersResponseBodyOkJson {
+    pub data: Vec<SystemUser>,
+    pub paging: Option<Paging>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        let ep = SystemUsersEndpoint::new(1234567890, "TOKEN", None);
+        let req = ep.render_request().unwrap();
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/1234567890/system_users?fields=id,name,role&access_token=TOKEN"
+        );
+
+        let res = Response::builder()
+            .status(StatusCode::OK)
+            .body(
+                br#"{"data":[{"id":"100089123456789","name":"Bot","role":"ADMIN"}],"paging":{"cursors":{"before":"B","after":"A"}}}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(res).unwrap() {
+            EndpointRet::Ok(ok_json) => {
+                assert_eq!(ok_json.data[0].id, 100089123456789);
+                assert_eq!(ok_json.data[0].role.as_deref(), Some("ADMIN"));
+                assert!(ok_json.paging.unwrap().next_cursor().is_none());
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+}
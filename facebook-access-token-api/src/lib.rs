@@ -2,3 +2,16 @@ pub use facebook_access_token;
 
 pub mod endpoints;
 pub mod objects;
+
+#[cfg(feature = "signed_request")]
+pub mod callback;
+
+pub mod login;
+
+#[cfg(feature = "oidc")]
+pub mod oidc;
+
+#[cfg(feature = "signed_request")]
+pub mod signed_request;
+
+pub mod token_manager;
@@ -0,0 +1,298 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/for-devices)
+
+use core::time::Duration;
+use std::time::Instant;
+
+use facebook_access_token::{AccessTokenExpiresIn, ClientAccessToken, ShortLivedUserAccessToken};
+use facebook_graph_api_object_error::Error;
+use http_api_client::{ClientRespondEndpointError, RetryableClient};
+use http_api_client_endpoint::http::StatusCode;
+
+use crate::{
+    endpoints::{
+        device_login::DeviceLoginResponseBodyOkJson, DeviceLoginStatusEndpoint, EndpointError,
+        EndpointRet,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+pub const DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE: i32 = 1349174;
+pub const DEVICE_LOGIN_SLOW_DOWN_SUBCODE: i32 = 1349172;
+pub const DEVICE_LOGIN_CODE_EXPIRED_SUBCODE: i32 = 1349152;
+
+// Added to the interval after a slow_down, the same as RFC 8628.
+pub const DEVICE_LOGIN_SLOW_DOWN_INTERVAL_INCREMENT: Duration = Duration::from_secs(5);
+// A missing or 0 interval would poll back to back, 5s is the RFC 8628 default.
+pub const DEVICE_LOGIN_INTERVAL_MIN: Duration = Duration::from_secs(5);
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceLoginStatusErrorCase {
+    AuthorizationPending,
+    SlowDown,
+    CodeExpired,
+}
+
+impl DeviceLoginStatusErrorCase {
+    pub fn from_error(error: &Error) -> Option<Self> {
+        match error.error_subcode {
+            Some(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE) => Some(Self::AuthorizationPending),
+            Some(DEVICE_LOGIN_SLOW_DOWN_SUBCODE) => Some(Self::SlowDown),
+            Some(DEVICE_LOGIN_CODE_EXPIRED_SUBCODE) => Some(Self::CodeExpired),
+            _ => None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::CodeExpired)
+    }
+}
+
+//
+// Polls device/login_status every interval until the user authorizes, the code expires or another error.
+// authorization_pending and slow_down are not returned.
+// Stops with Expired once the next poll would be after the expires_in of the device code.
+//
+pub async fn poll_device_login_status<C: RetryableClient + Send + Sync>(
+    client: &C,
+    client_access_token: &ClientAccessToken,
+    device_login: &DeviceLoginResponseBodyOkJson,
+) -> Result<
+    Result<
+        (ShortLivedUserAccessToken, Option<AccessTokenExpiresIn>),
+        (StatusCode, ResponseBodyErrJson),
+    >,
+    PollDeviceLoginStatusError<C::RespondError>,
+> {
+    let ep = DeviceLoginStatusEndpoint::new(client_access_token.inner(), &device_login.code, None);
+    let mut interval = Duration::from_secs(device_login.interval).max(DEVICE_LOGIN_INTERVAL_MIN);
+    let expires_in = Duration::from_secs(device_login.expires_in);
+
+    let started_at = Instant::now();
+    let mut slept = Duration::ZERO;
+
+    loop {
+        // slept covers clients whose sleep does not take real time.
+        if slept.max(started_at.elapsed()) + interval > expires_in {
+            return Err(PollDeviceLoginStatusError::Expired);
+        }
+
+        client.sleep(interval).await;
+        slept += interval;
+
+        let ret = client.respond_endpoint(&ep).await?;
+
+        match ret {
+            EndpointRet::Ok(ok_json) => {
+                return Ok(Ok((
+                    ok_json.access_token.into(),
+                    ok_json.expires_in.map(Into::into),
+                )))
+            }
+            EndpointRet::Other((status_code, Ok(err_json))) => {
+                match DeviceLoginStatusErrorCase::from_error(&err_json.error) {
+                    Some(DeviceLoginStatusErrorCase::AuthorizationPending) => {}
+                    Some(DeviceLoginStatusErrorCase::SlowDown) => {
+                        interval += DEVICE_LOGIN_SLOW_DOWN_INTERVAL_INCREMENT;
+                    }
+                    Some(DeviceLoginStatusErrorCase::CodeExpired) | None => {
+                        return Ok(Err((status_code, err_json)))
+                    }
+                }
+            }
+            EndpointRet::Other((status_code, Err(body))) => {
+                return Ok(Err((
+                    status_code,
+                    ResponseBodyErrJson::new(Error::new_with_status_code_and_body(
+                        status_code.as_u16(),
+                        String::from_utf8_lossy(&body).as_ref(),
+                    )),
+                )))
+            }
+        }
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum PollDeviceLoginStatusError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    RespondEndpointFailed(ClientRespondEndpointError<RE, EndpointError, EndpointError>),
+    Expired,
+}
+
+impl<RE> From<ClientRespondEndpointError<RE, EndpointError, EndpointError>>
+    for PollDeviceLoginStatusError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn from(err: ClientRespondEndpointError<RE, EndpointError, EndpointError>) -> Self {
+        Self::RespondEndpointFailed(err)
+    }
+}
+
+impl<RE> core::fmt::Display for PollDeviceLoginStatusError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<RE> std::error::Error for PollDeviceLoginStatusError<RE> where
+    RE: std::error::Error + Send + Sync + 'static
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{io::Error as IoError, sync::Mutex};
+
+    use futures_lite::future::block_on;
+    use http_api_client::{async_trait, Client};
+    use http_api_client_endpoint::{Body, Request, Response};
+
+    struct MockClient {
+        responses: Mutex<Vec<(u16, String)>>,
+        sleeps: Mutex<Vec<Duration>>,
+    }
+
+    #[async_trait]
+    impl Client for MockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            assert_eq!(request.uri().path(), "/v15.0/device/login_status");
+            let (status, body) = self.responses.lock().unwrap().remove(0);
+            Ok(Response::builder()
+                .status(status)
+                .body(body.into_bytes())
+                .unwrap())
+        }
+    }
+
+    #[async_trait]
+    impl RetryableClient for MockClient {
+        async fn sleep(&self, dur: Duration) {
+            self.sleeps.lock().unwrap().push(dur);
+        }
+    }
+
+    fn err_body(error_subcode: i32) -> String {
+        format!(
+            r#"{{"error":{{"message":"x","type":"OAuthException","code":31,"error_subcode":{error_subcode},"fbtrace_id":"x"}}}}"#
+        )
+    }
+
+    #[test]
+    fn test_poll_device_login_status() {
+        block_on(async {
+            let device_login = DeviceLoginResponseBodyOkJson {
+                code: "CODE".into(),
+                user_code: "A1NWZ9".into(),
+                verification_uri: "https://www.facebook.com/device".into(),
+                expires_in: 420,
+                interval: 5,
+            };
+            let client_access_token = ClientAccessToken::new(123, "CLIENT_TOKEN");
+
+            //
+            let client = MockClient {
+                responses: Mutex::new(vec![
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                    (400, err_body(DEVICE_LOGIN_SLOW_DOWN_SUBCODE)),
+                    (
+                        200,
+                        r#"{"access_token":"TOKEN","data_access_expiration_time":1676526788,"expires_in":5183944}"#.into(),
+                    ),
+                ]),
+                sleeps: Mutex::new(vec![]),
+            };
+            let (token, expires_in) =
+                poll_device_login_status(&client, &client_access_token, &device_login)
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(token.inner(), "TOKEN");
+            assert_eq!(expires_in.unwrap().into_inner(), 5183944);
+            assert_eq!(
+                *client.sleeps.lock().unwrap(),
+                vec![
+                    Duration::from_secs(5),
+                    Duration::from_secs(5),
+                    Duration::from_secs(10)
+                ]
+            );
+
+            //
+            let client = MockClient {
+                responses: Mutex::new(vec![
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                    (400, err_body(DEVICE_LOGIN_CODE_EXPIRED_SUBCODE)),
+                ]),
+                sleeps: Mutex::new(vec![]),
+            };
+            let (status_code, err_json) =
+                poll_device_login_status(&client, &client_access_token, &device_login)
+                    .await
+                    .unwrap()
+                    .unwrap_err();
+            assert_eq!(status_code, StatusCode::BAD_REQUEST);
+            assert_eq!(
+                DeviceLoginStatusErrorCase::from_error(&err_json.error),
+                Some(DeviceLoginStatusErrorCase::CodeExpired)
+            );
+
+            //
+            let device_login = DeviceLoginResponseBodyOkJson {
+                expires_in: 12,
+                ..device_login
+            };
+            let client = MockClient {
+                responses: Mutex::new(vec![
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                ]),
+                sleeps: Mutex::new(vec![]),
+            };
+            match poll_device_login_status(&client, &client_access_token, &device_login).await {
+                Err(PollDeviceLoginStatusError::Expired) => {}
+                x => panic!("{x:?}"),
+            }
+            assert_eq!(
+                *client.sleeps.lock().unwrap(),
+                vec![Duration::from_secs(5), Duration::from_secs(5)]
+            );
+
+            //
+            let device_login = DeviceLoginResponseBodyOkJson {
+                interval: 0,
+                ..device_login
+            };
+            let client = MockClient {
+                responses: Mutex::new(vec![
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                    (400, err_body(DEVICE_LOGIN_AUTHORIZATION_PENDING_SUBCODE)),
+                ]),
+                sleeps: Mutex::new(vec![]),
+            };
+            match poll_device_login_status(&client, &client_access_token, &device_login).await {
+                Err(PollDeviceLoginStatusError::Expired) => {}
+                x => panic!("{x:?}"),
+            }
+            assert_eq!(
+                *client.sleeps.lock().unwrap(),
+                vec![Duration::from_secs(5), Duration::from_secs(5)]
+            );
+        })
+    }
+}
@@ -0,0 +1,214 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/advanced/manual-flow#login)
+
+use facebook_permission::FacebookPermission;
+use url::{ParseError as UrlParseError, Url};
+
+use crate::endpoints::VERSION;
+#[cfg(feature = "pkce")]
+use crate::login::pkce::PkceCodeChallenge;
+
+//
+pub const URL_BASE: &str = "https://www.facebook.com";
+
+//
+#[derive(Debug, Clone)]
+pub struct LoginDialogUrlBuilder {
+    pub client_id: u64,
+    pub redirect_uri: Box<str>,
+    pub state: Option<Box<str>>,
+    pub scope: Vec<FacebookPermission>,
+    pub response_type: Option<LoginDialogResponseType>,
+    pub auth_type: Option<LoginDialogAuthType>,
+    #[cfg(feature = "pkce")]
+    pub code_challenge: Option<PkceCodeChallenge>,
+    //
+    pub version: Option<Box<str>>,
+}
+
+impl LoginDialogUrlBuilder {
+    pub fn new(
+        client_id: u64,
+        redirect_uri: impl AsRef<str>,
+        version: impl Into<Option<Box<str>>>,
+    ) -> Self {
+        Self {
+            client_id,
+            redirect_uri: redirect_uri.as_ref().into(),
+            state: None,
+            scope: vec![],
+            response_type: None,
+            auth_type: None,
+            #[cfg(feature = "pkce")]
+            code_challenge: None,
+            version: version.into(),
+        }
+    }
+
+    pub fn state(mut self, value: impl AsRef<str>) -> Self {
+        self.state = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn scope(mut self, value: impl IntoIterator<Item = FacebookPermission>) -> Self {
+        self.scope = value.into_iter().collect();
+        self
+    }
+
+    pub fn response_type(mut self, value: LoginDialogResponseType) -> Self {
+        self.response_type = Some(value);
+        self
+    }
+
+    pub fn auth_type(mut self, value: LoginDialogAuthType) -> Self {
+        self.auth_type = Some(value);
+        self
+    }
+
+    #[cfg(feature = "pkce")]
+    pub fn code_challenge(mut self, value: PkceCodeChallenge) -> Self {
+        self.code_challenge = Some(value);
+        self
+    }
+
+    pub fn build(&self) -> Result<Url, UrlParseError> {
+        let url = format!(
+            "{}/{}/dialog/oauth",
+            URL_BASE,
+            self.version.as_deref().unwrap_or(VERSION),
+        );
+        let mut url = Url::parse(&url)?;
+
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id.to_string())
+            .append_pair("redirect_uri", &self.redirect_uri);
+
+        if let Some(state) = &self.state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+
+        if !self.scope.is_empty() {
+            url.query_pairs_mut().append_pair(
+                "scope",
+                self.scope
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+                    .as_str(),
+            );
+        }
+
+        if let Some(response_type) = &self.response_type {
+            url.query_pairs_mut()
+                .append_pair("response_type", response_type.to_string().as_str());
+        }
+
+        if let Some(auth_type) = &self.auth_type {
+            url.query_pairs_mut()
+                .append_pair("auth_type", auth_type.to_string().as_str());
+        }
+
+        #[cfg(feature = "pkce")]
+        if let Some(code_challenge) = &self.code_challenge {
+            url.query_pairs_mut()
+                .append_pair("code_challenge", &code_challenge.value)
+                .append_pair(
+                    "code_challenge_method",
+                    code_challenge.method.to_string().as_str(),
+                );
+        }
+
+        Ok(url)
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginDialogResponseType {
+    Code,
+    Token,
+    CodeAndToken,
+    GrantedScopes,
+}
+
+impl core::fmt::Display for LoginDialogResponseType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Code => write!(f, "code"),
+            Self::Token => write!(f, "token"),
+            Self::CodeAndToken => write!(f, "code token"),
+            Self::GrantedScopes => write!(f, "granted_scopes"),
+        }
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginDialogAuthType {
+    Rerequest,
+    Reauthenticate,
+    Reauthorize,
+}
+
+impl core::fmt::Display for LoginDialogAuthType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Rerequest => write!(f, "rerequest"),
+            Self::Reauthenticate => write!(f, "reauthenticate"),
+            Self::Reauthorize => write!(f, "reauthorize"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "pkce")]
+    use crate::login::pkce::{PkceCodeChallengeMethod, PkceCodeVerifier};
+
+    #[test]
+    fn test_build() {
+        let url = LoginDialogUrlBuilder::new(123, "https://example.com/cb", None)
+            .build()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://www.facebook.com/v15.0/dialog/oauth?client_id=123&redirect_uri=https%3A%2F%2Fexample.com%2Fcb"
+        );
+
+        let url = LoginDialogUrlBuilder::new(123, "https://example.com/cb", None)
+            .state("STATE")
+            .scope([
+                FacebookPermission::PagesShowList,
+                FacebookPermission::PagesReadEngagement,
+            ])
+            .response_type(LoginDialogResponseType::Code)
+            .auth_type(LoginDialogAuthType::Rerequest)
+            .build()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://www.facebook.com/v15.0/dialog/oauth?client_id=123&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&state=STATE&scope=pages_show_list%2Cpages_read_engagement&response_type=code&auth_type=rerequest"
+        );
+    }
+
+    #[cfg(feature = "pkce")]
+    #[test]
+    fn test_build_with_code_challenge() {
+        let url = LoginDialogUrlBuilder::new(123, "https://example.com/cb", None)
+            .state("STATE")
+            .response_type(LoginDialogResponseType::Code)
+            .code_challenge(
+                PkceCodeVerifier::new("dBjftJeZ4CVP-mB92K9uhvBc0ylH7-Yc2cjM8cT2E7s")
+                    .unwrap()
+                    .code_challenge(PkceCodeChallengeMethod::S256),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://www.facebook.com/v15.0/dialog/oauth?client_id=123&redirect_uri=https%3A%2F%2Fexample.com%2Fcb&state=STATE&response_type=code&code_challenge=it6oTiYBEbUWHI2OaGm2FGfGvcaIJdpOyfNP-gqe48g&code_challenge_method=S256"
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/advanced/manual-flow)
+
+//
+pub mod device;
+
+pub use device::{
+    poll_device_login_status, DeviceLoginStatusErrorCase, PollDeviceLoginStatusError,
+};
+
+//
+pub mod dialog;
+
+pub use dialog::{LoginDialogAuthType, LoginDialogResponseType, LoginDialogUrlBuilder};
+
+//
+#[cfg(feature = "pkce")]
+pub mod pkce;
+
+#[cfg(feature = "pkce")]
+pub use pkce::{
+    PkceCodeChallenge, PkceCodeChallengeMethod, PkceCodeVerifier, PkceCodeVerifierError,
+};
+
+//
+#[cfg(feature = "pkce")]
+pub mod state;
+
+#[cfg(feature = "pkce")]
+pub use state::LoginState;
@@ -0,0 +1,160 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/guides/advanced/oidc-token/)
+//! [Ref](https://www.rfc-editor.org/rfc/rfc7636)
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use facebook_access_token::secret::redact;
+use rand::RngCore as _;
+use sha2::{Digest as _, Sha256};
+
+//
+pub const PKCE_CODE_VERIFIER_LEN_MIN: usize = 43;
+pub const PKCE_CODE_VERIFIER_LEN_MAX: usize = 128;
+
+//
+#[derive(Clone, PartialEq, Eq)]
+pub struct PkceCodeVerifier(Box<str>);
+
+impl PkceCodeVerifier {
+    // 43-128 characters of [A-Z] / [a-z] / [0-9] / "-" / "." / "_" / "~".
+    pub fn new(value: impl AsRef<str>) -> Result<Self, PkceCodeVerifierError> {
+        let value = value.as_ref();
+        if !(PKCE_CODE_VERIFIER_LEN_MIN..=PKCE_CODE_VERIFIER_LEN_MAX).contains(&value.len()) {
+            return Err(PkceCodeVerifierError::LengthInvalid(value.len()));
+        }
+        if let Some(c) = value
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')))
+        {
+            return Err(PkceCodeVerifierError::CharInvalid(c));
+        }
+        Ok(Self(value.into()))
+    }
+
+    // 43 characters, the minimum length allowed by RFC 7636.
+    pub fn generate() -> Self {
+        let mut bytes = [0_u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(URL_SAFE_NO_PAD.encode(bytes).into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn reveal(&self) -> &str {
+        &self.0
+    }
+
+    pub fn code_challenge(&self, method: PkceCodeChallengeMethod) -> PkceCodeChallenge {
+        let value = match method {
+            PkceCodeChallengeMethod::S256 => URL_SAFE_NO_PAD
+                .encode(Sha256::digest(self.0.as_bytes()))
+                .into(),
+            PkceCodeChallengeMethod::Plain => self.0.to_owned(),
+        };
+        PkceCodeChallenge { value, method }
+    }
+}
+
+impl core::fmt::Debug for PkceCodeVerifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PkceCodeVerifier")
+            .field(&redact(&self.0))
+            .finish()
+    }
+}
+
+impl core::fmt::Display for PkceCodeVerifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", redact(&self.0))
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PkceCodeVerifierError {
+    LengthInvalid(usize),
+    CharInvalid(char),
+}
+
+impl core::fmt::Display for PkceCodeVerifierError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for PkceCodeVerifierError {}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkceCodeChallenge {
+    pub value: Box<str>,
+    pub method: PkceCodeChallengeMethod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PkceCodeChallengeMethod {
+    S256,
+    Plain,
+}
+
+impl core::fmt::Display for PkceCodeChallengeMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::S256 => write!(f, "S256"),
+            Self::Plain => write!(f, "plain"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge() {
+        let code_verifier =
+            PkceCodeVerifier::new("dBjftJeZ4CVP-mB92K9uhvBc0ylH7-Yc2cjM8cT2E7s").unwrap();
+        assert_eq!(
+            code_verifier.code_challenge(PkceCodeChallengeMethod::S256),
+            PkceCodeChallenge {
+                value: "it6oTiYBEbUWHI2OaGm2FGfGvcaIJdpOyfNP-gqe48g".into(),
+                method: PkceCodeChallengeMethod::S256,
+            }
+        );
+        assert_eq!(
+            code_verifier.code_challenge(PkceCodeChallengeMethod::Plain),
+            PkceCodeChallenge {
+                value: "dBjftJeZ4CVP-mB92K9uhvBc0ylH7-Yc2cjM8cT2E7s".into(),
+                method: PkceCodeChallengeMethod::Plain,
+            }
+        );
+
+        assert!(!format!("{code_verifier:?}").contains(code_verifier.as_str()));
+        assert!(!code_verifier.to_string().contains(code_verifier.as_str()));
+        assert_eq!(code_verifier.reveal(), code_verifier.as_str());
+
+        let code_verifier = PkceCodeVerifier::generate();
+        assert_eq!(code_verifier.as_str().len(), 43);
+        assert_ne!(code_verifier, PkceCodeVerifier::generate());
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PkceCodeVerifier::new("a".repeat(43)).is_ok());
+        assert!(PkceCodeVerifier::new("a".repeat(128)).is_ok());
+        assert!(PkceCodeVerifier::new("aZ09-._~".repeat(6)).is_ok());
+        assert_eq!(
+            PkceCodeVerifier::new("a".repeat(42)),
+            Err(PkceCodeVerifierError::LengthInvalid(42))
+        );
+        assert_eq!(
+            PkceCodeVerifier::new("a".repeat(129)),
+            Err(PkceCodeVerifierError::LengthInvalid(129))
+        );
+        assert_eq!(
+            PkceCodeVerifier::new(format!("{}+", "a".repeat(42))),
+            Err(PkceCodeVerifierError::CharInvalid('+'))
+        );
+    }
+}
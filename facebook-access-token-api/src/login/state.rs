@@ -0,0 +1,58 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/security#stateparam)
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::RngCore as _;
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginState(Box<str>);
+
+impl LoginState {
+    pub fn new(value: impl AsRef<str>) -> Self {
+        Self(value.as_ref().into())
+    }
+
+    pub fn generate() -> Self {
+        let mut bytes = [0_u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(URL_SAFE_NO_PAD.encode(bytes).into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // Constant-time comparison with the state returned to redirect_uri.
+    pub fn verify(&self, returned_state: impl AsRef<str>) -> bool {
+        let a = self.0.as_bytes();
+        let b = returned_state.as_ref().as_bytes();
+        if a.len() != b.len() {
+            return false;
+        }
+        a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
+
+impl core::fmt::Display for LoginState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_and_verify() {
+        let state = LoginState::generate();
+        assert_eq!(state.as_str().len(), 43);
+        assert_ne!(state, LoginState::generate());
+
+        assert!(state.verify(state.as_str()));
+        assert!(!state.verify(LoginState::generate().as_str()));
+        assert!(!state.verify(""));
+        assert!(LoginState::new("abc").verify("abc"));
+        assert!(!LoginState::new("abc").verify("abd"));
+    }
+}
@@ -0,0 +1,49 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/reference/user/accounts/)
+
+use facebook_access_token::PageAccessToken;
+use serde::{Deserialize, Serialize};
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Account {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub id: u64,
+    pub name: String,
+    // Not returned for the pages the user has no tasks on.
+    pub access_token: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
+
+impl Account {
+    pub const fn fields() -> &'static str {
+        "id,name,access_token,category,tasks"
+    }
+
+    // None when the page has no access_token.
+    pub fn to_managed_page(&self, never_expires: bool) -> Option<ManagedPage> {
+        Some(ManagedPage {
+            id: self.id,
+            name: self.name.to_owned(),
+            category: self.category.to_owned(),
+            tasks: self.tasks.to_owned(),
+            access_token: self.access_token.to_owned()?.into(),
+            never_expires,
+        })
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedPage {
+    pub id: u64,
+    pub name: String,
+    pub category: Option<String>,
+    pub tasks: Vec<String>,
+    pub access_token: PageAccessToken,
+    // https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#long-lived-page-token
+    // true when got via a LongLivedUserAccessToken, otherwise it expires with the user access token.
+    pub never_expires: bool,
+}
@@ -2,6 +2,7 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use facebook_access_token::{AccessTokenKind, AccessTokenWithMetadata};
 use facebook_graph_api_object_error::Error;
 use facebook_permission::FacebookPermission;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -12,12 +13,118 @@ use serde_json::Value;
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DebugTokenResult {
     pub is_valid: bool,
-    pub scopes: Vec<String>,
+    pub scopes: Vec<FacebookPermission>,
     pub error: Option<Error>,
     #[serde(flatten)]
     pub type_extra: Option<DebugTokenResultTypeExtra>,
 }
 
+impl DebugTokenResult {
+    pub fn to_access_token_with_metadata<T>(&self, token: T) -> AccessTokenWithMetadata<T> {
+        let mut t = AccessTokenWithMetadata::new(token);
+        t.scopes = self.scopes.iter().map(|x| x.to_string()).collect();
+
+        // The fields User, Page and SystemUser have in common.
+        let user_type_extra = match &self.type_extra {
+            Some(DebugTokenResultTypeExtra::App(extra)) => {
+                t.app_id = Some(extra.app_id);
+                None
+            }
+            Some(DebugTokenResultTypeExtra::User(extra)) => Some((
+                extra.app_id,
+                extra.user_id,
+                extra.issued_at,
+                extra.expires(),
+                extra.data_access_expires_at,
+            )),
+            Some(DebugTokenResultTypeExtra::Page(extra)) => Some((
+                extra.app_id,
+                extra.user_id,
+                extra.issued_at,
+                extra.expires(),
+                extra.data_access_expires_at,
+            )),
+            Some(DebugTokenResultTypeExtra::SystemUser(extra)) => Some((
+                extra.app_id,
+                extra.user_id,
+                extra.issued_at,
+                extra.expires(),
+                extra.data_access_expires_at,
+            )),
+            None => None,
+        };
+
+        if let Some((app_id, user_id, issued_at, expires, data_access_expires_at)) = user_type_extra
+        {
+            t.app_id = Some(app_id);
+            t.user_id = Some(user_id);
+            t.issued_at = issued_at;
+            t.expires_at = match expires {
+                DebugTokenResultExpires::Never => None,
+                DebugTokenResultExpires::Date(dt) => Some(dt),
+            };
+            t.data_access_expires_at =
+                Some(data_access_expires_at).filter(|dt| dt.timestamp() != 0);
+        }
+
+        t
+    }
+
+    // None when the token is invalid and Facebook omits the type.
+    pub fn kind(&self) -> Option<AccessTokenKind> {
+        match &self.type_extra {
+            Some(DebugTokenResultTypeExtra::App(_)) => Some(AccessTokenKind::App),
+            Some(DebugTokenResultTypeExtra::User(_)) => Some(AccessTokenKind::User),
+            Some(DebugTokenResultTypeExtra::Page(_)) => Some(AccessTokenKind::Page),
+            Some(DebugTokenResultTypeExtra::SystemUser(_)) => Some(AccessTokenKind::SystemUser),
+            None => None,
+        }
+    }
+
+    pub fn granular_scopes(&self) -> Option<&[DebugTokenResultUserTypeExtraGranularScope]> {
+        match &self.type_extra {
+            Some(DebugTokenResultTypeExtra::User(extra)) => extra.granular_scopes.as_deref(),
+            Some(DebugTokenResultTypeExtra::Page(extra)) => extra.granular_scopes.as_deref(),
+            Some(DebugTokenResultTypeExtra::SystemUser(extra)) => extra.granular_scopes.as_deref(),
+            _ => None,
+        }
+    }
+
+    // Err contains the permissions that are not in scopes.
+    pub fn require(
+        &self,
+        permissions: &[FacebookPermission],
+    ) -> Result<(), Vec<FacebookPermission>> {
+        let missing = permissions
+            .iter()
+            .filter(|x| !self.scopes.contains(x))
+            .cloned()
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    // target_id is a Page or an Instagram account id.
+    // A granted permission without target_ids in granular_scopes covers every target.
+    pub fn granted_for_target(&self, permission: &FacebookPermission, target_id: u64) -> bool {
+        if !self.scopes.contains(permission) {
+            return false;
+        }
+
+        match self
+            .granular_scopes()
+            .and_then(|x| x.iter().find(|x| &x.scope == permission))
+            .and_then(|x| x.target_ids.as_ref())
+        {
+            Some(target_ids) => target_ids.iter().any(|x| *x as u64 == target_id),
+            None => true,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum DebugTokenResultTypeExtra {
@@ -27,6 +134,8 @@ pub enum DebugTokenResultTypeExtra {
     User(DebugTokenResultUserTypeExtra),
     #[serde(rename = "PAGE")]
     Page(DebugTokenResultPageTypeExtra),
+    #[serde(rename = "SYSTEM_USER")]
+    SystemUser(DebugTokenResultSystemUserTypeExtra),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -97,6 +206,34 @@ impl DebugTokenResultPageTypeExtra {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DebugTokenResultSystemUserTypeExtra {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub app_id: u64,
+    pub application: String,
+    //
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub user_id: u64,
+    #[serde(default, with = "ts_seconds_option")]
+    pub issued_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, with = "ts_seconds")]
+    pub data_access_expires_at: DateTime<Utc>,
+    //
+    pub granular_scopes: Option<Vec<DebugTokenResultUserTypeExtraGranularScope>>,
+}
+
+impl DebugTokenResultSystemUserTypeExtra {
+    pub fn expires(&self) -> DebugTokenResultExpires {
+        if self.expires_at.timestamp() == 0 {
+            DebugTokenResultExpires::Never
+        } else {
+            DebugTokenResultExpires::Date(self.expires_at)
+        }
+    }
+}
+
 //
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DebugTokenResultUserTypeExtraGranularScope {
@@ -133,3 +270,86 @@ pub enum DebugTokenResultExpires {
     Never,
     Date(DateTime<Utc>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use facebook_access_token::{AppAccessToken, PageAccessToken, UserAccessToken};
+
+    use crate::endpoints::debug_token::DebugTokenResponseBodyOkJson;
+
+    #[test]
+    fn test_to_access_token_with_metadata() {
+        let content = include_str!(
+            "../../tests/response_body_json_files/debug_token__user_access_token_2.json"
+        );
+        let ok_json = serde_json::from_str::<DebugTokenResponseBodyOkJson>(content).unwrap();
+        let t = ok_json
+            .data
+            .to_access_token_with_metadata(UserAccessToken::from("x"));
+        assert_eq!(t.app_id, Some(257422819769992));
+        assert_eq!(t.user_id, Some(123));
+        assert_eq!(t.issued_at.unwrap().timestamp(), 1668750788);
+        assert_eq!(t.expires_at.unwrap().timestamp(), 1673934788);
+        assert_eq!(t.data_access_expires_at.unwrap().timestamp(), 1676526788);
+        assert_eq!(t.scopes, vec!["public_profile"]);
+        assert!(t.is_expired(Utc::now()));
+
+        let content = include_str!(
+            "../../tests/response_body_json_files/debug_token__page_access_token.json"
+        );
+        let ok_json = serde_json::from_str::<DebugTokenResponseBodyOkJson>(content).unwrap();
+        assert_eq!(ok_json.data.kind(), Some(AccessTokenKind::Page));
+        let t = ok_json
+            .data
+            .to_access_token_with_metadata(PageAccessToken::from("x"));
+        assert_eq!(t.user_id, Some(123));
+        assert!(t.issued_at.is_none());
+        assert_eq!(t.expires_at.unwrap().timestamp(), 1669086000);
+
+        let content =
+            include_str!("../../tests/response_body_json_files/debug_token__app_access_token.json");
+        let ok_json = serde_json::from_str::<DebugTokenResponseBodyOkJson>(content).unwrap();
+        assert_eq!(ok_json.data.kind(), Some(AccessTokenKind::App));
+        let t = ok_json
+            .data
+            .to_access_token_with_metadata(AppAccessToken::from("x"));
+        assert_eq!(t.app_id, Some(257422819769992));
+        assert!(t.user_id.is_none());
+        assert!(t.expires_at.is_none());
+        assert!(!t.is_expired(Utc::now()));
+    }
+
+    #[test]
+    fn test_require_and_granted_for_target() {
+        let content = include_str!(
+            "../../tests/response_body_json_files/debug_token__user_access_token_example_1.json"
+        );
+        let ok_json = serde_json::from_str::<DebugTokenResponseBodyOkJson>(content).unwrap();
+        let result = ok_json.data;
+
+        assert!(result
+            .require(&[
+                FacebookPermission::PagesShowList,
+                FacebookPermission::InstagramBasic
+            ])
+            .is_ok());
+        assert_eq!(
+            result.require(&[
+                FacebookPermission::PagesShowList,
+                FacebookPermission::PagesReadEngagement,
+                FacebookPermission::PagesManageMetadata,
+            ]),
+            Err(vec![
+                FacebookPermission::PagesReadEngagement,
+                FacebookPermission::PagesManageMetadata
+            ])
+        );
+
+        assert!(result.granted_for_target(&FacebookPermission::PagesShowList, 109004958110000));
+        assert!(!result.granted_for_target(&FacebookPermission::PagesShowList, 1));
+        assert!(result.granted_for_target(&FacebookPermission::InstagramBasic, 1));
+        assert!(!result.granted_for_target(&FacebookPermission::PagesReadEngagement, 1));
+    }
+}
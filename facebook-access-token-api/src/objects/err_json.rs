@@ -1,4 +1,6 @@
 use facebook_graph_api_object_error::Error;
+#[cfg(feature = "usage")]
+use facebook_graph_api_rate_limit::Usage;
 use serde::{Deserialize, Serialize};
 
 //
@@ -9,6 +11,21 @@ debug_token endpoint error same as access_token endpoint grant_type=fb_exchange_
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ErrJson {
     pub error: Error,
+    // Only filled by the endpoints that parse the usage headers,
+    // e.g. to see how close to the limit the app was when it got ApiTooManyCalls.
+    #[cfg(feature = "usage")]
+    #[serde(skip)]
+    pub usage: Box<Usage>,
+}
+
+impl ErrJson {
+    pub fn new(error: Error) -> Self {
+        Self {
+            error,
+            #[cfg(feature = "usage")]
+            usage: Default::default(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,32 @@
+//! [Ref](https://www.rfc-editor.org/rfc/rfc7517)
+
+use serde::{Deserialize, Serialize};
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    pub fn find(&self, kid: Option<&str>) -> Option<&Jwk> {
+        match kid {
+            Some(kid) => self.keys.iter().find(|x| x.kid.as_deref() == Some(kid)),
+            None if self.keys.len() == 1 => self.keys.first(),
+            None => None,
+        }
+    }
+}
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Jwk {
+    pub kid: Option<String>,
+    pub kty: String,
+    pub alg: Option<String>,
+    pub r#use: Option<String>,
+    // base64url encoded
+    pub n: String,
+    // base64url encoded
+    pub e: String,
+}
@@ -1,3 +1,8 @@
+//
+pub mod account;
+
+pub use account::{Account, ManagedPage};
+
 //
 pub mod debug_token;
 
@@ -7,3 +12,18 @@ pub use debug_token::DebugTokenResult;
 pub mod err_json;
 
 pub use err_json::ErrJson as ResponseBodyErrJson;
+
+//
+pub mod jwks;
+
+pub use jwks::{Jwk, Jwks};
+
+//
+pub mod permission;
+
+pub use permission::Permission;
+
+//
+pub mod system_user;
+
+pub use system_user::SystemUser;
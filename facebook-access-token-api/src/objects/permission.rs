@@ -0,0 +1,11 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/reference/user/permissions/)
+
+use facebook_permission::{FacebookPermission, FacebookPermissionStatus};
+use serde::{Deserialize, Serialize};
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Permission {
+    pub permission: FacebookPermission,
+    pub status: FacebookPermissionStatus,
+}
@@ -0,0 +1,20 @@
+//! [Ref](https://developers.facebook.com/docs/marketing-api/reference/system-user/)
+
+use serde::{Deserialize, Serialize};
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SystemUser {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub id: u64,
+    pub name: String,
+    // ADMIN or EMPLOYEE
+    pub role: Option<String>,
+}
+
+impl SystemUser {
+    pub const fn fields() -> &'static str {
+        "id,name,role"
+    }
+}
@@ -0,0 +1,362 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/limited-login/token/validating)
+
+use core::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{serde::ts_seconds, DateTime, Utc};
+use facebook_access_token::OidcIdToken;
+use rsa::{
+    pkcs1v15::{Signature, VerifyingKey},
+    signature::Verifier as _,
+    BigUint, RsaPublicKey,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+
+use crate::oidc::jwks_provider::JwksProvider;
+
+//
+pub const ISSUERS: &[&str] = &["https://www.facebook.com", "https://limited.facebook.com"];
+pub const LEEWAY_DEFAULT: Duration = Duration::from_secs(60);
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcIdTokenHeader {
+    pub alg: String,
+    pub kid: Option<String>,
+    pub typ: Option<String>,
+}
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcIdTokenClaims {
+    pub iss: String,
+    pub aud: String,
+    pub sub: String,
+    #[serde(with = "ts_seconds")]
+    pub iat: DateTime<Utc>,
+    #[serde(with = "ts_seconds")]
+    pub exp: DateTime<Utc>,
+    pub jti: Option<String>,
+    pub nonce: Option<String>,
+    pub at_hash: Option<String>,
+    //
+    pub name: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub email: Option<String>,
+    pub picture: Option<String>,
+    //
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct OidcIdTokenVerifier<P> {
+    provider: P,
+    app_id: u64,
+    leeway: Duration,
+}
+
+impl<P> OidcIdTokenVerifier<P>
+where
+    P: JwksProvider + Send + Sync,
+{
+    pub fn new(provider: P, app_id: u64) -> Self {
+        Self {
+            provider,
+            app_id,
+            leeway: LEEWAY_DEFAULT,
+        }
+    }
+
+    pub fn leeway(mut self, value: Duration) -> Self {
+        self.leeway = value;
+        self
+    }
+
+    // nonce is the one passed to the Limited Login, a token without it is rejected.
+    pub async fn verify(
+        &self,
+        id_token: &OidcIdToken,
+        nonce: &str,
+        now: DateTime<Utc>,
+    ) -> Result<OidcIdTokenClaims, OidcIdTokenVerifyError<P::Error>> {
+        let mut split = id_token.inner().split('.');
+        let (header_str, claims_str, signature_str) =
+            match (split.next(), split.next(), split.next(), split.next()) {
+                (Some(header_str), Some(claims_str), Some(signature_str), None) => {
+                    (header_str, claims_str, signature_str)
+                }
+                _ => return Err(OidcIdTokenVerifyError::Malformed),
+            };
+
+        let header: OidcIdTokenHeader = decode_json(header_str)?;
+        if header.alg != "RS256" {
+            return Err(OidcIdTokenVerifyError::UnsupportedAlgorithm(header.alg));
+        }
+
+        //
+        let jwk = self
+            .provider
+            .jwk(header.kid.as_deref())
+            .await
+            .map_err(OidcIdTokenVerifyError::JwksProviderFailed)?
+            .ok_or_else(|| OidcIdTokenVerifyError::KeyNotFound(header.kid.to_owned()))?;
+        if jwk.kty != "RSA" {
+            return Err(OidcIdTokenVerifyError::KeyNotFound(header.kid.to_owned()));
+        }
+
+        let public_key = RsaPublicKey::new(
+            BigUint::from_bytes_be(&decode_base64(&jwk.n)?),
+            BigUint::from_bytes_be(&decode_base64(&jwk.e)?),
+        )
+        .map_err(OidcIdTokenVerifyError::InvalidKey)?;
+        let signature = Signature::try_from(decode_base64(signature_str)?.as_slice())
+            .map_err(|_| OidcIdTokenVerifyError::InvalidSignature)?;
+        VerifyingKey::<Sha256>::new(public_key)
+            .verify(format!("{header_str}.{claims_str}").as_bytes(), &signature)
+            .map_err(|_| OidcIdTokenVerifyError::InvalidSignature)?;
+
+        //
+        let claims: OidcIdTokenClaims = decode_json(claims_str)?;
+
+        if !ISSUERS.contains(&claims.iss.as_str()) {
+            return Err(OidcIdTokenVerifyError::InvalidIssuer(claims.iss));
+        }
+        if claims.aud != self.app_id.to_string() {
+            return Err(OidcIdTokenVerifyError::InvalidAudience(claims.aud));
+        }
+        let leeway = chrono::Duration::from_std(self.leeway).unwrap_or_default();
+        if claims.exp + leeway <= now {
+            return Err(OidcIdTokenVerifyError::Expired(claims.exp));
+        }
+        match claims.nonce.as_deref() {
+            Some(x) if x == nonce => {}
+            Some(_) => return Err(OidcIdTokenVerifyError::NonceMismatch),
+            None => return Err(OidcIdTokenVerifyError::NonceMissing),
+        }
+
+        Ok(claims)
+    }
+}
+
+fn decode_base64<PE>(s: &str) -> Result<Vec<u8>, OidcIdTokenVerifyError<PE>>
+where
+    PE: std::error::Error + Send + Sync + 'static,
+{
+    URL_SAFE_NO_PAD
+        .decode(s.trim_end_matches('='))
+        .map_err(OidcIdTokenVerifyError::InvalidBase64)
+}
+
+fn decode_json<T: DeserializeOwned, PE>(s: &str) -> Result<T, OidcIdTokenVerifyError<PE>>
+where
+    PE: std::error::Error + Send + Sync + 'static,
+{
+    serde_json::from_slice(&decode_base64(s)?).map_err(OidcIdTokenVerifyError::InvalidJson)
+}
+
+//
+#[derive(Debug)]
+pub enum OidcIdTokenVerifyError<PE>
+where
+    PE: std::error::Error + Send + Sync + 'static,
+{
+    Malformed,
+    InvalidBase64(base64::DecodeError),
+    InvalidJson(serde_json::Error),
+    UnsupportedAlgorithm(String),
+    JwksProviderFailed(PE),
+    KeyNotFound(Option<String>),
+    InvalidKey(rsa::Error),
+    InvalidSignature,
+    InvalidIssuer(String),
+    InvalidAudience(String),
+    Expired(DateTime<Utc>),
+    NonceMissing,
+    NonceMismatch,
+}
+
+impl<PE> core::fmt::Display for OidcIdTokenVerifyError<PE>
+where
+    PE: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<PE> std::error::Error for OidcIdTokenVerifyError<PE> where
+    PE: std::error::Error + Send + Sync + 'static
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+    use futures_lite::future::block_on;
+    use rsa::{
+        pkcs1v15::SigningKey, pkcs8::DecodePrivateKey as _, signature::SignatureEncoding as _,
+        signature::Signer as _, traits::PublicKeyParts as _, RsaPrivateKey,
+    };
+
+    use crate::objects::{Jwk, Jwks};
+
+    fn private_key() -> RsaPrivateKey {
+        RsaPrivateKey::from_pkcs8_pem(include_str!(
+            "../../tests/oidc_id_token_files/private_key.pem"
+        ))
+        .unwrap()
+    }
+
+    fn jwks() -> Jwks {
+        let public_key = private_key().to_public_key();
+        Jwks {
+            keys: vec![Jwk {
+                kid: Some("KID".into()),
+                kty: "RSA".into(),
+                alg: Some("RS256".into()),
+                r#use: Some("sig".into()),
+                n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+                e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+            }],
+        }
+    }
+
+    fn sign(header: &str, claims: &str) -> OidcIdToken {
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header),
+            URL_SAFE_NO_PAD.encode(claims)
+        );
+        let signature = SigningKey::<Sha256>::new(private_key()).sign(message.as_bytes());
+        format!(
+            "{}.{}",
+            message,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        )
+        .into()
+    }
+
+    #[test]
+    fn test_verify() {
+        block_on(async {
+            let verifier = OidcIdTokenVerifier::new(jwks(), 123);
+            let now = Utc.timestamp_opt(1668750788, 0).unwrap();
+            let header = r#"{"alg":"RS256","kid":"KID","typ":"JWT"}"#;
+            let claims = r#"{"iss":"https://www.facebook.com","aud":"123","sub":"456","iat":1668750000,"exp":1668753600,"jti":"x","nonce":"NONCE","name":"Foo"}"#;
+
+            //
+            let claims_ret = verifier
+                .verify(&sign(header, claims), "NONCE", now)
+                .await
+                .unwrap();
+            assert_eq!(claims_ret.sub, "456");
+            assert_eq!(claims_ret.name.as_deref(), Some("Foo"));
+
+            //
+            match verifier.verify(&sign(header, claims), "OTHER", now).await {
+                Err(OidcIdTokenVerifyError::NonceMismatch) => {}
+                x => panic!("{x:?}"),
+            }
+
+            match verifier
+                .verify(
+                    &sign(header, claims),
+                    "NONCE",
+                    Utc.timestamp_opt(1668753600 + 60, 0).unwrap(),
+                )
+                .await
+            {
+                Err(OidcIdTokenVerifyError::Expired(_)) => {}
+                x => panic!("{x:?}"),
+            }
+
+            match OidcIdTokenVerifier::new(jwks(), 789)
+                .verify(&sign(header, claims), "NONCE", now)
+                .await
+            {
+                Err(OidcIdTokenVerifyError::InvalidAudience(aud)) => assert_eq!(aud, "123"),
+                x => panic!("{x:?}"),
+            }
+
+            match verifier
+                .verify(
+                    &sign(header, &claims.replace("www.facebook.com", "example.com")),
+                    "NONCE",
+                    now,
+                )
+                .await
+            {
+                Err(OidcIdTokenVerifyError::InvalidIssuer(_)) => {}
+                x => panic!("{x:?}"),
+            }
+
+            //
+            let id_token = sign(header, claims);
+            let (message, _) = id_token.inner().rsplit_once('.').unwrap();
+            let (_, other_signature) = sign(header, &claims.replace("456", "457"))
+                .inner()
+                .rsplit_once('.')
+                .map(|(a, b)| (a.to_owned(), b.to_owned()))
+                .unwrap();
+            match verifier
+                .verify(&format!("{message}.{other_signature}").into(), "NONCE", now)
+                .await
+            {
+                Err(OidcIdTokenVerifyError::InvalidSignature) => {}
+                x => panic!("{x:?}"),
+            }
+
+            match verifier
+                .verify(
+                    &sign(r#"{"alg":"HS256","kid":"KID"}"#, claims),
+                    "NONCE",
+                    now,
+                )
+                .await
+            {
+                Err(OidcIdTokenVerifyError::UnsupportedAlgorithm(alg)) => assert_eq!(alg, "HS256"),
+                x => panic!("{x:?}"),
+            }
+
+            match verifier
+                .verify(
+                    &sign(r#"{"alg":"RS256","kid":"OTHER"}"#, claims),
+                    "NONCE",
+                    now,
+                )
+                .await
+            {
+                Err(OidcIdTokenVerifyError::KeyNotFound(kid)) => {
+                    assert_eq!(kid.as_deref(), Some("OTHER"))
+                }
+                x => panic!("{x:?}"),
+            }
+
+            match verifier
+                .verify(
+                    &sign(header, &claims.replace(r#","nonce":"NONCE""#, "")),
+                    "NONCE",
+                    now,
+                )
+                .await
+            {
+                Err(OidcIdTokenVerifyError::NonceMissing) => {}
+                x => panic!("{x:?}"),
+            }
+
+            match verifier.verify(&"a.b".into(), "NONCE", now).await {
+                Err(OidcIdTokenVerifyError::Malformed) => {}
+                x => panic!("{x:?}"),
+            }
+        })
+    }
+}
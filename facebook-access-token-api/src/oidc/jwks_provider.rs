@@ -0,0 +1,213 @@
+use core::{convert::Infallible, time::Duration};
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+use http_api_client::{async_trait, Client, ClientRespondEndpointError};
+use http_api_client_endpoint::{http::StatusCode, Body};
+
+use crate::{
+    endpoints::{EndpointError, EndpointRet, OidcJwksEndpoint},
+    objects::{Jwk, Jwks, ResponseBodyErrJson},
+};
+
+//
+pub const JWKS_CACHE_TTL_DEFAULT: Duration = Duration::from_secs(3600);
+// An unknown kid refetches the key set at most this often, so that forged kids can't make every verify fetch it.
+pub const JWKS_REFETCH_INTERVAL_MIN: Duration = Duration::from_secs(60);
+
+//
+#[async_trait]
+pub trait JwksProvider: Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn jwks(&self) -> Result<Jwks, Self::Error>;
+
+    // The key the id_token header refers to.
+    async fn jwk(&self, kid: Option<&str>) -> Result<Option<Jwk>, Self::Error> {
+        Ok(self.jwks().await?.find(kid).cloned())
+    }
+}
+
+// A local key set, e.g. for tests or a cached copy.
+#[async_trait]
+impl JwksProvider for Jwks {
+    type Error = Infallible;
+
+    async fn jwks(&self) -> Result<Jwks, Self::Error> {
+        Ok(self.to_owned())
+    }
+}
+
+//
+// Caches the key set for ttl, an unknown kid refetches it, e.g. after a key rotation.
+//
+#[derive(Debug, Clone)]
+pub struct ClientJwksProvider<C> {
+    client: C,
+    endpoint: OidcJwksEndpoint,
+    ttl: Duration,
+    cache: Arc<Mutex<Option<(Instant, Jwks)>>>,
+}
+
+impl<C> ClientJwksProvider<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            endpoint: OidcJwksEndpoint::new(),
+            ttl: JWKS_CACHE_TTL_DEFAULT,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn endpoint(mut self, value: OidcJwksEndpoint) -> Self {
+        self.endpoint = value;
+        self
+    }
+
+    pub fn ttl(mut self, value: Duration) -> Self {
+        self.ttl = value;
+        self
+    }
+
+    // The cache only holds public keys, a panic while it was locked leaves nothing half written.
+    fn cached(&self) -> Option<(Duration, Jwks)> {
+        self.cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map(|(fetched_at, jwks)| (fetched_at.elapsed(), jwks.to_owned()))
+    }
+}
+
+impl<C> ClientJwksProvider<C>
+where
+    C: Client + Send + Sync,
+{
+    async fn fetch(&self) -> Result<Jwks, ClientJwksProviderError<C::RespondError>> {
+        let jwks = match self
+            .client
+            .respond_endpoint(&self.endpoint)
+            .await
+            .map_err(ClientJwksProviderError::RespondFailed)?
+        {
+            EndpointRet::Ok(jwks) => jwks,
+            EndpointRet::Other(x) => return Err(ClientJwksProviderError::Other(x)),
+        };
+
+        *self.cache.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((Instant::now(), jwks.to_owned()));
+
+        Ok(jwks)
+    }
+}
+
+#[async_trait]
+impl<C> JwksProvider for ClientJwksProvider<C>
+where
+    C: Client + Send + Sync,
+{
+    type Error = ClientJwksProviderError<C::RespondError>;
+
+    async fn jwks(&self) -> Result<Jwks, Self::Error> {
+        match self.cached() {
+            Some((age, jwks)) if age < self.ttl => Ok(jwks),
+            _ => self.fetch().await,
+        }
+    }
+
+    async fn jwk(&self, kid: Option<&str>) -> Result<Option<Jwk>, Self::Error> {
+        if let Some((age, jwks)) = self.cached() {
+            if age < self.ttl {
+                if let Some(jwk) = jwks.find(kid) {
+                    return Ok(Some(jwk.to_owned()));
+                }
+                if age < JWKS_REFETCH_INTERVAL_MIN {
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(self.fetch().await?.find(kid).cloned())
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum ClientJwksProviderError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    RespondFailed(ClientRespondEndpointError<RE, EndpointError, EndpointError>),
+    Other((StatusCode, Result<ResponseBodyErrJson, Body>)),
+}
+
+impl<RE> core::fmt::Display for ClientJwksProviderError<RE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<RE> std::error::Error for ClientJwksProviderError<RE> where
+    RE: std::error::Error + Send + Sync + 'static
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        io::Error as IoError,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use futures_lite::future::block_on;
+    use http_api_client::{Request, Response};
+
+    #[derive(Debug, Default)]
+    struct MockClient {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Client for MockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            _request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            Ok(Response::builder()
+                .status(200)
+                .body(br#"{"keys":[{"kid":"KID","kty":"RSA","alg":"RS256","use":"sig","n":"AQAB","e":"AQAB"}]}"#.to_vec())
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn test_client_jwks_provider_cache() {
+        block_on(async {
+            let provider = ClientJwksProvider::new(MockClient::default());
+            assert!(provider.jwk(Some("KID")).await.unwrap().is_some());
+            assert!(provider.jwk(Some("KID")).await.unwrap().is_some());
+            assert!(provider.jwks().await.is_ok());
+            assert_eq!(provider.client.requests.load(Ordering::SeqCst), 1);
+
+            // Unknown kid, the key set was just fetched.
+            assert!(provider.jwk(Some("OTHER")).await.unwrap().is_none());
+            assert_eq!(provider.client.requests.load(Ordering::SeqCst), 1);
+
+            //
+            let provider = ClientJwksProvider::new(MockClient::default()).ttl(Duration::ZERO);
+            assert!(provider.jwk(Some("KID")).await.unwrap().is_some());
+            assert!(provider.jwk(Some("KID")).await.unwrap().is_some());
+            assert_eq!(provider.client.requests.load(Ordering::SeqCst), 2);
+        })
+    }
+}
@@ -0,0 +1,13 @@
+//! [Ref](https://developers.facebook.com/docs/facebook-login/limited-login/token/validating)
+
+//
+pub mod id_token;
+
+pub use id_token::{OidcIdTokenClaims, OidcIdTokenVerifier, OidcIdTokenVerifyError};
+
+//
+pub mod jwks_provider;
+
+pub use jwks_provider::{
+    ClientJwksProvider, ClientJwksProviderError, JwksProvider, JWKS_CACHE_TTL_DEFAULT,
+};
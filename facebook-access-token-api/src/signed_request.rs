@@ -0,0 +1,198 @@
+//! [Ref](https://developers.facebook.com/docs/games/gamesonfacebook/login#parsingsr)
+//! [Ref](https://developers.facebook.com/docs/development/create-an-app/app-dashboard/data-deletion-callback)
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use facebook_access_token::{secret::redact, AppAccessToken};
+use hmac::{Hmac, Mac as _};
+use serde::{Deserialize, Serialize};
+use serde_aux::field_attributes::deserialize_option_number_from_string;
+use serde_json::{Map, Value};
+use sha2::Sha256;
+
+//
+pub const ALGORITHM_HMAC_SHA256: &str = "HMAC-SHA256";
+
+//
+#[derive(Debug, Clone)]
+pub struct SignedRequest {
+    pub signature: Vec<u8>,
+    pub payload: SignedRequestPayload,
+}
+
+impl SignedRequest {
+    pub fn parse_and_verify(
+        raw: impl AsRef<str>,
+        app_secret: impl AsRef<str>,
+    ) -> Result<Self, SignedRequestError> {
+        let (signature_str, payload_str) = raw
+            .as_ref()
+            .split_once('.')
+            .ok_or(SignedRequestError::Malformed)?;
+
+        // The payload is untrusted until the signature is verified, so it is only decoded after that.
+        let signature = decode_base64(signature_str)?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(app_secret.as_ref().as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(payload_str.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| SignedRequestError::BadSignature)?;
+
+        let payload: SignedRequestPayload = serde_json::from_slice(&decode_base64(payload_str)?)
+            .map_err(SignedRequestError::InvalidJson)?;
+
+        if !payload
+            .algorithm
+            .eq_ignore_ascii_case(ALGORITHM_HMAC_SHA256)
+        {
+            return Err(SignedRequestError::UnsupportedAlgorithm(payload.algorithm));
+        }
+
+        Ok(Self { signature, payload })
+    }
+
+    pub fn parse_and_verify_with_app_access_token(
+        raw: impl AsRef<str>,
+        app_access_token: &AppAccessToken,
+    ) -> Result<Self, SignedRequestError> {
+        let (_, app_secret) = app_access_token
+            .app_id_and_app_secret()
+            .ok_or(SignedRequestError::AppSecretMissing)?;
+        Self::parse_and_verify(raw, app_secret)
+    }
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, SignedRequestError> {
+    URL_SAFE_NO_PAD
+        .decode(s.trim_end_matches('='))
+        .map_err(SignedRequestError::InvalidBase64)
+}
+
+//
+#[derive(Deserialize, Serialize, Clone)]
+pub struct SignedRequestPayload {
+    pub algorithm: String,
+    #[serde(with = "ts_seconds")]
+    pub issued_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub user_id: Option<u64>,
+    pub code: Option<String>,
+    #[serde(default, with = "ts_seconds_option")]
+    pub expires: Option<DateTime<Utc>>,
+    pub oauth_token: Option<String>,
+    //
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl core::fmt::Debug for SignedRequestPayload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SignedRequestPayload")
+            .field("algorithm", &self.algorithm)
+            .field("issued_at", &self.issued_at)
+            .field("user_id", &self.user_id)
+            .field("code", &self.code.as_deref().map(redact))
+            .field("expires", &self.expires)
+            .field("oauth_token", &self.oauth_token.as_deref().map(redact))
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum SignedRequestError {
+    Malformed,
+    InvalidBase64(base64::DecodeError),
+    InvalidJson(serde_json::Error),
+    UnsupportedAlgorithm(String),
+    BadSignature,
+    AppSecretMissing,
+}
+
+impl core::fmt::Display for SignedRequestError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for SignedRequestError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "Fa5Y_8gSHSmMqAGJpoiCX1RXwJ9aSy0IoQuW3xcTsNo.eyJhbGdvcml0aG0iOiJITUFDLVNIQTI1NiIsImV4cGlyZXMiOjEyOTE4NDA0MDAsImlzc3VlZF9hdCI6MTI5MTgzNjgwMCwib2F1dGhfdG9rZW4iOiJUT0tFTiIsInVzZXJfaWQiOiIxMjMiLCJjb2RlIjoiQ09ERSJ9";
+
+    #[test]
+    fn test_parse_and_verify() {
+        let signed_request = SignedRequest::parse_and_verify(RAW, "APP_SECRET").unwrap();
+        assert_eq!(signed_request.payload.algorithm, "HMAC-SHA256");
+        assert_eq!(signed_request.payload.issued_at.timestamp(), 1291836800);
+        assert_eq!(signed_request.payload.user_id, Some(123));
+        assert_eq!(signed_request.payload.code.as_deref(), Some("CODE"));
+        assert_eq!(
+            signed_request.payload.expires.map(|x| x.timestamp()),
+            Some(1291840400)
+        );
+        assert_eq!(signed_request.payload.oauth_token.as_deref(), Some("TOKEN"));
+        let debug = format!("{signed_request:?}");
+        assert!(
+            !debug.contains("\"TOKEN\"") && !debug.contains("\"CODE\""),
+            "{debug}"
+        );
+
+        let signed_request = SignedRequest::parse_and_verify_with_app_access_token(
+            RAW,
+            &AppAccessToken::with_app_secret(1, "APP_SECRET"),
+        )
+        .unwrap();
+        assert_eq!(signed_request.payload.user_id, Some(123));
+
+        //
+        match SignedRequest::parse_and_verify(RAW, "OTHER_SECRET") {
+            Err(SignedRequestError::BadSignature) => {}
+            x => panic!("{x:?}"),
+        }
+        match SignedRequest::parse_and_verify_with_app_access_token(RAW, &AppAccessToken::from("1"))
+        {
+            Err(SignedRequestError::AppSecretMissing) => {}
+            x => panic!("{x:?}"),
+        }
+        match SignedRequest::parse_and_verify(
+            "XvclW9P7JRaevfkHN6xH-ntmT-r6VAy6kBpQ5L2Bq8c.eyJhbGdvcml0aG0iOiJITUFDLVNIQTEiLCJpc3N1ZWRfYXQiOjEyOTE4MzY4MDB9",
+            "APP_SECRET",
+        ) {
+            Err(SignedRequestError::UnsupportedAlgorithm(algorithm)) => {
+                assert_eq!(algorithm, "HMAC-SHA1")
+            }
+            x => panic!("{x:?}"),
+        }
+        // An unsigned payload is never decoded.
+        match SignedRequest::parse_and_verify(
+            "Fa5Y_8gSHSmMqAGJpoiCX1RXwJ9aSy0IoQuW3xcTsNo.bm90IGpzb24",
+            "APP_SECRET",
+        ) {
+            Err(SignedRequestError::BadSignature) => {}
+            x => panic!("{x:?}"),
+        }
+        match SignedRequest::parse_and_verify(
+            "Cql2-o-dB87_3Ts14fBVTFVAWk62oyKcs5y1tXpcqOE.bm90IGpzb24",
+            "APP_SECRET",
+        ) {
+            Err(SignedRequestError::InvalidJson(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        match SignedRequest::parse_and_verify("!!!.eyJ9", "APP_SECRET") {
+            Err(SignedRequestError::InvalidBase64(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        match SignedRequest::parse_and_verify("abc", "APP_SECRET") {
+            Err(SignedRequestError::Malformed) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,592 @@
+//! Keeps long-lived user access tokens and page access tokens fresh.
+//!
+//! Tokens are checked with debug_token via the app access token,
+//! long-lived user access tokens that expire within refresh_before are re-exchanged with fb_exchange_token.
+
+use core::time::Duration;
+use std::sync::mpsc::Sender;
+
+use chrono::{DateTime, Utc};
+use facebook_access_token::{secret::redact, AccessTokenWithMetadata, AppAccessToken};
+use facebook_graph_api_object_error::{Error, KnownErrorCase};
+use http_api_client::{Client, ClientRespondEndpointError};
+use http_api_client_endpoint::http::StatusCode;
+
+use crate::{
+    endpoints::{
+        debug_page_access_token_via_app_access_token, debug_user_access_token_via_app_access_token,
+        get_long_lived_user_access_token, EndpointError,
+    },
+    objects::ResponseBodyErrJson,
+};
+
+//
+pub mod store;
+
+pub use store::{ManagedAccessToken, MemoryTokenStore, TokenStore};
+
+//
+pub const REFRESH_BEFORE_DEFAULT: Duration = Duration::from_secs(3600 * 24 * 7);
+
+//
+//
+//
+pub struct TokenManager<C, S> {
+    client: C,
+    store: S,
+    app_id: u64,
+    app_secret: Box<str>,
+    refresh_before: Duration,
+    events: Option<Sender<TokenManagerEvent>>,
+    clock: fn() -> DateTime<Utc>,
+}
+
+impl<C, S> TokenManager<C, S>
+where
+    C: Client + Send + Sync,
+    S: TokenStore + Send + Sync,
+{
+    pub fn new(client: C, store: S, app_id: u64, app_secret: impl AsRef<str>) -> Self {
+        Self {
+            client,
+            store,
+            app_id,
+            app_secret: app_secret.as_ref().into(),
+            refresh_before: REFRESH_BEFORE_DEFAULT,
+            events: None,
+            clock: Utc::now,
+        }
+    }
+
+    pub fn refresh_before(mut self, value: Duration) -> Self {
+        self.refresh_before = value;
+        self
+    }
+
+    pub fn events(mut self, sender: Sender<TokenManagerEvent>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    // Defaults to Utc::now, e.g. a fixed time in tests.
+    pub fn clock(mut self, value: fn() -> DateTime<Utc>) -> Self {
+        self.clock = value;
+        self
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub async fn refresh_all(
+        &self,
+    ) -> Result<
+        Vec<(
+            String,
+            Result<TokenRefreshOutcome, TokenManagerError<C::RespondError, S::Error>>,
+        )>,
+        S::Error,
+    > {
+        let mut rets = vec![];
+        for key in self.store.keys().await? {
+            let ret = self.refresh(&key).await;
+            rets.push((key, ret));
+        }
+        Ok(rets)
+    }
+
+    pub async fn refresh(
+        &self,
+        key: &str,
+    ) -> Result<TokenRefreshOutcome, TokenManagerError<C::RespondError, S::Error>> {
+        let token = self
+            .store
+            .get(key)
+            .await
+            .map_err(TokenManagerError::StoreFailed)?
+            .ok_or(TokenManagerError::NotFound)?;
+
+        let app_access_token = AppAccessToken::with_app_secret(self.app_id, &self.app_secret);
+
+        let debug_result = match &token {
+            ManagedAccessToken::LongLivedUser(t) => {
+                debug_user_access_token_via_app_access_token(
+                    &self.client,
+                    t.token.inner(),
+                    app_access_token,
+                )
+                .await
+            }
+            ManagedAccessToken::Page(t) => {
+                debug_page_access_token_via_app_access_token(
+                    &self.client,
+                    t.token.inner(),
+                    app_access_token,
+                )
+                .await
+            }
+        }
+        .map_err(TokenManagerError::RespondFailed)?;
+
+        let debug_result = match debug_result {
+            Ok(debug_result) => debug_result,
+            Err(x) => return Ok(self.failed(key, TokenRefreshFailureReason::Rejected(x))),
+        };
+
+        if !debug_result.is_valid {
+            return Ok(self.failed(key, TokenRefreshFailureReason::Invalid(debug_result.error)));
+        }
+
+        let now = (self.clock)();
+        match token {
+            ManagedAccessToken::LongLivedUser(t) => {
+                let t = debug_result.to_access_token_with_metadata(t.token);
+                if !t.expires_within(self.refresh_before, now) {
+                    self.put(key, ManagedAccessToken::LongLivedUser(t)).await?;
+                    return Ok(TokenRefreshOutcome::Fresh);
+                }
+
+                let issued_at = now;
+                match get_long_lived_user_access_token(
+                    &self.client,
+                    self.app_id,
+                    &self.app_secret,
+                    t.token.inner(),
+                )
+                .await
+                .map_err(TokenManagerError::RespondFailed)?
+                {
+                    Ok((token, expires_in)) => {
+                        let new_t =
+                            AccessTokenWithMetadata::with_expires_in(token, issued_at, expires_in);
+                        let new_t = AccessTokenWithMetadata {
+                            data_access_expires_at: t.data_access_expires_at,
+                            scopes: t.scopes,
+                            app_id: t.app_id,
+                            user_id: t.user_id,
+                            ..new_t
+                        };
+                        self.put(key, ManagedAccessToken::LongLivedUser(new_t))
+                            .await?;
+
+                        self.send(TokenManagerEvent::Refreshed {
+                            key: key.to_owned(),
+                        });
+                        Ok(TokenRefreshOutcome::Refreshed)
+                    }
+                    Err(x) => Ok(self.failed(key, TokenRefreshFailureReason::Rejected(x))),
+                }
+            }
+            ManagedAccessToken::Page(t) => {
+                let t = debug_result.to_access_token_with_metadata(t.token);
+                let expires_within = t.expires_within(self.refresh_before, now);
+                self.put(key, ManagedAccessToken::Page(t)).await?;
+
+                if !expires_within {
+                    return Ok(TokenRefreshOutcome::Fresh);
+                }
+
+                // A page access token can only be renewed from the long-lived user access token it was got from.
+                Ok(self.failed(key, TokenRefreshFailureReason::NotRefreshable))
+            }
+        }
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        token: ManagedAccessToken,
+    ) -> Result<(), TokenManagerError<C::RespondError, S::Error>> {
+        self.store
+            .put(key, token)
+            .await
+            .map_err(TokenManagerError::StoreFailed)
+    }
+
+    fn failed(&self, key: &str, reason: TokenRefreshFailureReason) -> TokenRefreshOutcome {
+        self.send(TokenManagerEvent::RefreshFailed {
+            key: key.to_owned(),
+            reason,
+        });
+        TokenRefreshOutcome::Failed
+    }
+
+    fn send(&self, event: TokenManagerEvent) {
+        if let Some(events) = &self.events {
+            // The receiver may be dropped, events are best effort.
+            let _ = events.send(event);
+        }
+    }
+}
+
+impl<C, S> core::fmt::Debug for TokenManager<C, S>
+where
+    C: core::fmt::Debug,
+    S: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TokenManager")
+            .field("client", &self.client)
+            .field("store", &self.store)
+            .field("app_id", &self.app_id)
+            .field("app_secret", &redact(&self.app_secret))
+            .field("refresh_before", &self.refresh_before)
+            .field("events", &self.events)
+            .finish()
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenRefreshOutcome {
+    Fresh,
+    Refreshed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub enum TokenManagerEvent {
+    Refreshed {
+        key: String,
+    },
+    RefreshFailed {
+        key: String,
+        reason: TokenRefreshFailureReason,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum TokenRefreshFailureReason {
+    Invalid(Option<Error>),
+    NotRefreshable,
+    Rejected((StatusCode, ResponseBodyErrJson)),
+}
+
+impl TokenRefreshFailureReason {
+    pub fn to_known_error_case(&self) -> Option<KnownErrorCase> {
+        match self {
+            Self::Invalid(Some(error)) => error
+                .to_known_error_case()
+                .or(Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid)),
+            Self::Invalid(None) => Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid),
+            Self::NotRefreshable => None,
+            Self::Rejected((_, err_json)) => err_json.error.to_known_error_case(),
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub enum TokenManagerError<RE, SE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+    SE: std::error::Error + Send + Sync + 'static,
+{
+    NotFound,
+    StoreFailed(SE),
+    RespondFailed(ClientRespondEndpointError<RE, EndpointError, EndpointError>),
+}
+
+impl<RE, SE> core::fmt::Display for TokenManagerError<RE, SE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+    SE: std::error::Error + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<RE, SE> std::error::Error for TokenManagerError<RE, SE>
+where
+    RE: std::error::Error + Send + Sync + 'static,
+    SE: std::error::Error + Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{io::Error as IoError, sync::mpsc::channel};
+
+    use facebook_access_token::{LongLivedUserAccessToken, PageAccessToken};
+    use futures_lite::future::block_on;
+    use http_api_client::{async_trait, Body, Request, Response};
+
+    #[derive(Debug)]
+    struct MockClient {
+        debug_token: (u16, String),
+        access_token: (u16, String),
+    }
+
+    #[async_trait]
+    impl Client for MockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let (status, body) = match request.uri().path() {
+                "/v15.0/debug_token" => &self.debug_token,
+                "/v15.0/oauth/access_token" => &self.access_token,
+                path => panic!("unexpected path {path}"),
+            };
+            Ok(Response::builder()
+                .status(*status)
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        }
+    }
+
+    fn debug_token_body(r#type: &str, expires_at: i64) -> String {
+        format!(
+            r#"{{"data":{{"app_id":"1","type":"{type}","application":"x","data_access_expires_at":{data_access_expires_at},"expires_at":{expires_at},"is_valid":true,"scopes":["public_profile"],"user_id":"123","profile_id":"456"}}}}"#,
+            r#type = r#type,
+            data_access_expires_at = Utc::now().timestamp() + 3600 * 24 * 90,
+            expires_at = expires_at,
+        )
+    }
+
+    fn new_manager(
+        debug_token: (u16, String),
+        access_token: (u16, String),
+    ) -> (
+        TokenManager<MockClient, MemoryTokenStore>,
+        std::sync::mpsc::Receiver<TokenManagerEvent>,
+    ) {
+        let (tx, rx) = channel();
+        let manager = TokenManager::new(
+            MockClient {
+                debug_token,
+                access_token,
+            },
+            MemoryTokenStore::new(),
+            1,
+            "APP_SECRET",
+        )
+        .events(tx);
+        (manager, rx)
+    }
+
+    #[test]
+    fn test_debug_redacts_app_secret() {
+        let (manager, _rx) = new_manager((500, "".into()), (500, "".into()));
+        let debug = format!("{manager:?}");
+        assert!(debug.contains("app_id: 1"));
+        assert!(!debug.contains("APP_SECRET"));
+    }
+
+    #[test]
+    fn test_refresh_long_lived_user_access_token() {
+        block_on(async {
+            //
+            let (manager, rx) = new_manager(
+                (
+                    200,
+                    debug_token_body("USER", Utc::now().timestamp() + 3600 * 24 * 30),
+                ),
+                (500, "".into()),
+            );
+            manager
+                .store()
+                .put(
+                    "u",
+                    ManagedAccessToken::LongLivedUser(AccessTokenWithMetadata::new(
+                        LongLivedUserAccessToken::from("OLD"),
+                    )),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                manager.refresh("u").await.unwrap(),
+                TokenRefreshOutcome::Fresh
+            );
+            match manager.store().get("u").await.unwrap().unwrap() {
+                ManagedAccessToken::LongLivedUser(t) => {
+                    assert_eq!(t.token.inner(), "OLD");
+                    assert_eq!(t.user_id, Some(123));
+                    assert!(t.expires_at.is_some());
+                }
+                x => panic!("{x:?}"),
+            }
+            assert!(rx.try_recv().is_err());
+
+            //
+            let (manager, rx) = new_manager(
+                (
+                    200,
+                    debug_token_body("USER", Utc::now().timestamp() + 3600 * 24),
+                ),
+                (
+                    200,
+                    r#"{"access_token":"NEW","token_type":"bearer","expires_in":5183944}"#.into(),
+                ),
+            );
+            manager
+                .store()
+                .put(
+                    "u",
+                    ManagedAccessToken::LongLivedUser(AccessTokenWithMetadata::new(
+                        LongLivedUserAccessToken::from("OLD"),
+                    )),
+                )
+                .await
+                .unwrap();
+            let rets = manager.refresh_all().await.unwrap();
+            assert_eq!(rets.len(), 1);
+            assert_eq!(rets[0].1.as_ref().unwrap(), &TokenRefreshOutcome::Refreshed);
+            match manager.store().get("u").await.unwrap().unwrap() {
+                ManagedAccessToken::LongLivedUser(t) => {
+                    assert_eq!(t.token.inner(), "NEW");
+                    assert_eq!(t.user_id, Some(123));
+                    assert!(!t.expires_within(Duration::from_secs(3600 * 24 * 59), Utc::now()));
+                }
+                x => panic!("{x:?}"),
+            }
+            match rx.try_recv().unwrap() {
+                TokenManagerEvent::Refreshed { key } => assert_eq!(key, "u"),
+                x => panic!("{x:?}"),
+            }
+
+            //
+            let (manager, rx) = new_manager(
+                (
+                    200,
+                    include_str!(
+                        "../../tests/response_body_json_files/debug_token__user_access_token_3.json"
+                    )
+                    .into(),
+                ),
+                (500, "".into()),
+            );
+            manager
+                .store()
+                .put(
+                    "u",
+                    ManagedAccessToken::LongLivedUser(AccessTokenWithMetadata::new(
+                        LongLivedUserAccessToken::from("OLD"),
+                    )),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                manager.refresh("u").await.unwrap(),
+                TokenRefreshOutcome::Failed
+            );
+            match rx.try_recv().unwrap() {
+                TokenManagerEvent::RefreshFailed { key, reason } => {
+                    assert_eq!(key, "u");
+                    assert!(reason
+                        .to_known_error_case()
+                        .unwrap()
+                        .is_access_token_expired_or_revoked_or_invalid());
+                }
+                x => panic!("{x:?}"),
+            }
+        })
+    }
+
+    #[test]
+    fn test_refresh_with_clock() {
+        block_on(async {
+            // Expires at 2023-01-31, refresh_before is 7 days.
+            let debug_token = (200, debug_token_body("PAGE", 1675123200));
+
+            for (clock, outcome) in [
+                (
+                    (|| {
+                        DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+                            .unwrap()
+                            .into()
+                    }) as fn() -> DateTime<Utc>,
+                    TokenRefreshOutcome::Fresh,
+                ),
+                (
+                    || {
+                        DateTime::parse_from_rfc3339("2023-01-30T00:00:00Z")
+                            .unwrap()
+                            .into()
+                    },
+                    TokenRefreshOutcome::Failed,
+                ),
+            ] {
+                let (manager, _rx) = new_manager(debug_token.clone(), (500, "".into()));
+                let manager = manager.clock(clock);
+                manager
+                    .store()
+                    .put(
+                        "p",
+                        ManagedAccessToken::Page(AccessTokenWithMetadata::new(
+                            PageAccessToken::from("PAGE"),
+                        )),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(manager.refresh("p").await.unwrap(), outcome);
+            }
+        })
+    }
+
+    #[test]
+    fn test_refresh_page_access_token() {
+        block_on(async {
+            let (manager, rx) = new_manager((200, debug_token_body("PAGE", 0)), (500, "".into()));
+            manager
+                .store()
+                .put(
+                    "p",
+                    ManagedAccessToken::Page(AccessTokenWithMetadata::new(PageAccessToken::from(
+                        "PAGE",
+                    ))),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                manager.refresh("p").await.unwrap(),
+                TokenRefreshOutcome::Fresh
+            );
+            assert!(rx.try_recv().is_err());
+
+            let (manager, rx) = new_manager(
+                (200, debug_token_body("PAGE", Utc::now().timestamp() + 3600)),
+                (500, "".into()),
+            );
+            manager
+                .store()
+                .put(
+                    "p",
+                    ManagedAccessToken::Page(AccessTokenWithMetadata::new(PageAccessToken::from(
+                        "PAGE",
+                    ))),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                manager.refresh("p").await.unwrap(),
+                TokenRefreshOutcome::Failed
+            );
+            match rx.try_recv().unwrap() {
+                TokenManagerEvent::RefreshFailed {
+                    reason: TokenRefreshFailureReason::NotRefreshable,
+                    ..
+                } => {}
+                x => panic!("{x:?}"),
+            }
+
+            assert!(matches!(
+                manager.refresh("x").await,
+                Err(TokenManagerError::NotFound)
+            ));
+        })
+    }
+}
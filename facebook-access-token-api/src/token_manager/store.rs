@@ -0,0 +1,54 @@
+use core::convert::Infallible;
+use std::{collections::HashMap, sync::Mutex};
+
+use facebook_access_token::{AccessTokenWithMetadata, LongLivedUserAccessToken, PageAccessToken};
+use http_api_client::async_trait;
+
+//
+#[derive(Debug, Clone)]
+pub enum ManagedAccessToken {
+    LongLivedUser(AccessTokenWithMetadata<LongLivedUserAccessToken>),
+    Page(AccessTokenWithMetadata<PageAccessToken>),
+}
+
+//
+#[async_trait]
+pub trait TokenStore {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn get(&self, key: &str) -> Result<Option<ManagedAccessToken>, Self::Error>;
+
+    async fn put(&self, key: &str, token: ManagedAccessToken) -> Result<(), Self::Error>;
+
+    async fn keys(&self) -> Result<Vec<String>, Self::Error>;
+}
+
+//
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    inner: Mutex<HashMap<String, ManagedAccessToken>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryTokenStore {
+    type Error = Infallible;
+
+    async fn get(&self, key: &str) -> Result<Option<ManagedAccessToken>, Self::Error> {
+        Ok(self.inner.lock().expect("").get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, token: ManagedAccessToken) -> Result<(), Self::Error> {
+        self.inner.lock().expect("").insert(key.to_owned(), token);
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, Self::Error> {
+        Ok(self.inner.lock().expect("").keys().cloned().collect())
+    }
+}
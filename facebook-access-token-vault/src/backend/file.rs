@@ -0,0 +1,206 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use async_trait::async_trait;
+
+use crate::{backend::VaultBackend, SealedValue, VaultKey};
+
+//
+// Makes the temporary file of every write unique, so concurrent puts of the same key don't share it.
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+//
+// One JSON file per key, named `{app_id}_{subject_id}_{kind}.json`.
+// Writes go to a temporary file first and are renamed into place, the last rename wins.
+//
+// The std::fs calls are blocking and run on the calling task.
+// With an async runtime and a large vault (e.g. rekey_all), wrap the backend to run them on a blocking thread pool.
+//
+#[derive(Debug, Clone)]
+pub struct FileVaultBackend {
+    dir: PathBuf,
+}
+
+impl FileVaultBackend {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &VaultKey) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+#[async_trait]
+impl VaultBackend for FileVaultBackend {
+    type Error = FileVaultBackendError;
+
+    async fn get(&self, key: &VaultKey) -> Result<Option<SealedValue>, Self::Error> {
+        let bytes = match fs::read(self.path(key)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(FileVaultBackendError::ReadFailed(err)),
+        };
+
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(FileVaultBackendError::DeFailed)
+    }
+
+    async fn put(&self, key: &VaultKey, value: SealedValue) -> Result<(), Self::Error> {
+        let bytes = serde_json::to_vec(&value).map_err(FileVaultBackendError::SerFailed)?;
+
+        fs::create_dir_all(&self.dir).map_err(FileVaultBackendError::WriteFailed)?;
+        let tmp_path = self.dir.join(format!(
+            ".{key}.json.{}.{}.tmp",
+            process::id(),
+            TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp_path, bytes).map_err(FileVaultBackendError::WriteFailed)?;
+        if let Err(err) = fs::rename(&tmp_path, self.path(key)) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(FileVaultBackendError::WriteFailed(err));
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &VaultKey) -> Result<(), Self::Error> {
+        match fs::remove_file(self.path(key)) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(FileVaultBackendError::WriteFailed(err)),
+        }
+    }
+
+    async fn keys(&self) -> Result<Vec<VaultKey>, Self::Error> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(FileVaultBackendError::ReadFailed(err)),
+        };
+
+        let mut keys = vec![];
+        for dir_entry in read_dir {
+            let dir_entry = dir_entry.map_err(FileVaultBackendError::ReadFailed)?;
+            let file_name = dir_entry.file_name();
+            // Other files in the dir are ignored.
+            if let Some(key) = file_name
+                .to_str()
+                .and_then(|x| x.strip_suffix(".json"))
+                .and_then(|x| x.parse::<VaultKey>().ok())
+            {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum FileVaultBackendError {
+    ReadFailed(io::Error),
+    WriteFailed(io::Error),
+    SerFailed(serde_json::Error),
+    DeFailed(serde_json::Error),
+}
+
+impl core::fmt::Display for FileVaultBackendError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for FileVaultBackendError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use facebook_access_token::AccessTokenKind;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn test_get_put_remove_keys() {
+        block_on(async {
+            let dir = std::env::temp_dir().join(format!(
+                "facebook-access-token-vault-test-{}",
+                std::process::id()
+            ));
+            let backend = FileVaultBackend::new(&dir);
+
+            let key = VaultKey::new(123, 456, AccessTokenKind::Page);
+            assert!(backend.get(&key).await.unwrap().is_none());
+            assert!(backend.keys().await.unwrap().is_empty());
+
+            let value = SealedValue {
+                key_id: 1,
+                nonce: "NONCE".into(),
+                ciphertext: "CIPHERTEXT".into(),
+            };
+            backend.put(&key, value.clone()).await.unwrap();
+            assert_eq!(backend.get(&key).await.unwrap(), Some(value));
+            assert!(dir.join("123_456_page.json").exists());
+
+            fs::write(dir.join("README"), "").unwrap();
+            assert_eq!(backend.keys().await.unwrap(), vec![key]);
+
+            backend.remove(&key).await.unwrap();
+            backend.remove(&key).await.unwrap();
+            assert!(backend.get(&key).await.unwrap().is_none());
+
+            fs::remove_dir_all(&dir).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_concurrent_put() {
+        let dir = std::env::temp_dir().join(format!(
+            "facebook-access-token-vault-test-concurrent-{}",
+            std::process::id()
+        ));
+        let backend = FileVaultBackend::new(&dir);
+        let key = VaultKey::new(123, 456, AccessTokenKind::Page);
+
+        let handles = (0..8)
+            .map(|i| {
+                let backend = backend.clone();
+                std::thread::spawn(move || {
+                    block_on(async {
+                        for j in 0..20 {
+                            let value = SealedValue {
+                                key_id: i * 100 + j,
+                                nonce: "NONCE".into(),
+                                ciphertext: "CIPHERTEXT".into(),
+                            };
+                            backend.put(&key, value).await.unwrap();
+                        }
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        block_on(async {
+            assert!(backend.get(&key).await.unwrap().is_some());
+            assert_eq!(backend.keys().await.unwrap(), vec![key]);
+        });
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use core::convert::Infallible;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+
+use crate::{backend::VaultBackend, SealedValue, VaultKey};
+
+//
+#[derive(Debug, Default)]
+pub struct MemoryVaultBackend {
+    inner: Mutex<HashMap<VaultKey, SealedValue>>,
+}
+
+impl MemoryVaultBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Every change is a single HashMap call, a panic while it was locked leaves nothing half written.
+    fn lock(&self) -> MutexGuard<'_, HashMap<VaultKey, SealedValue>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl VaultBackend for MemoryVaultBackend {
+    type Error = Infallible;
+
+    async fn get(&self, key: &VaultKey) -> Result<Option<SealedValue>, Self::Error> {
+        Ok(self.lock().get(key).cloned())
+    }
+
+    async fn put(&self, key: &VaultKey, value: SealedValue) -> Result<(), Self::Error> {
+        self.lock().insert(*key, value);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &VaultKey) -> Result<(), Self::Error> {
+        self.lock().remove(key);
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<VaultKey>, Self::Error> {
+        Ok(self.lock().keys().copied().collect())
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::{SealedValue, VaultKey};
+
+//
+pub mod file;
+pub mod memory;
+
+pub use file::{FileVaultBackend, FileVaultBackendError};
+pub use memory::MemoryVaultBackend;
+
+//
+#[async_trait]
+pub trait VaultBackend {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn get(&self, key: &VaultKey) -> Result<Option<SealedValue>, Self::Error>;
+
+    async fn put(&self, key: &VaultKey, value: SealedValue) -> Result<(), Self::Error>;
+
+    async fn remove(&self, key: &VaultKey) -> Result<(), Self::Error>;
+
+    async fn keys(&self) -> Result<Vec<VaultKey>, Self::Error>;
+}
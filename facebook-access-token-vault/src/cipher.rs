@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+//
+pub type VaultCipherKeyId = u32;
+
+pub const VAULT_CIPHER_KEY_LEN: usize = 32;
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SealedValue {
+    pub key_id: VaultCipherKeyId,
+    // base64
+    pub nonce: String,
+    // base64
+    pub ciphertext: String,
+}
+
+//
+// New values are sealed with the current key, older keys are kept to open values sealed before a rotation.
+//
+#[derive(Clone)]
+pub struct VaultCipher {
+    current_key_id: VaultCipherKeyId,
+    keys: BTreeMap<VaultCipherKeyId, Aes256Gcm>,
+}
+
+impl VaultCipher {
+    pub fn new(key_id: VaultCipherKeyId, key: [u8; VAULT_CIPHER_KEY_LEN]) -> Self {
+        let mut keys = BTreeMap::new();
+        keys.insert(key_id, Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)));
+        Self {
+            current_key_id: key_id,
+            keys,
+        }
+    }
+
+    // Adds a previous key, used only to open values.
+    pub fn with_previous_key(
+        mut self,
+        key_id: VaultCipherKeyId,
+        key: [u8; VAULT_CIPHER_KEY_LEN],
+    ) -> Self {
+        self.keys
+            .entry(key_id)
+            .or_insert_with(|| Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)));
+        self
+    }
+
+    // The new key becomes current, the old ones are kept.
+    pub fn rotate(&mut self, key_id: VaultCipherKeyId, key: [u8; VAULT_CIPHER_KEY_LEN]) {
+        self.keys
+            .insert(key_id, Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)));
+        self.current_key_id = key_id;
+    }
+
+    // The current key can't be removed.
+    pub fn remove_key(&mut self, key_id: VaultCipherKeyId) -> bool {
+        if key_id == self.current_key_id {
+            return false;
+        }
+        self.keys.remove(&key_id).is_some()
+    }
+
+    pub fn current_key_id(&self) -> VaultCipherKeyId {
+        self.current_key_id
+    }
+
+    pub fn key_ids(&self) -> Vec<VaultCipherKeyId> {
+        self.keys.keys().copied().collect()
+    }
+
+    pub fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<SealedValue, VaultCipherError> {
+        let cipher = self
+            .keys
+            .get(&self.current_key_id)
+            .ok_or(VaultCipherError::UnknownKeyId(self.current_key_id))?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| VaultCipherError::SealFailed)?;
+
+        Ok(SealedValue {
+            key_id: self.current_key_id,
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub fn open(&self, sealed: &SealedValue, aad: &[u8]) -> Result<Vec<u8>, VaultCipherError> {
+        let cipher = self
+            .keys
+            .get(&sealed.key_id)
+            .ok_or(VaultCipherError::UnknownKeyId(sealed.key_id))?;
+        let nonce = STANDARD
+            .decode(&sealed.nonce)
+            .map_err(VaultCipherError::DecodeFailed)?;
+        if nonce.len() != 12 {
+            return Err(VaultCipherError::NonceInvalid);
+        }
+        let ciphertext = STANDARD
+            .decode(&sealed.ciphertext)
+            .map_err(VaultCipherError::DecodeFailed)?;
+
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| VaultCipherError::OpenFailed)
+    }
+}
+
+impl core::fmt::Debug for VaultCipher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VaultCipher")
+            .field("current_key_id", &self.current_key_id)
+            .field("key_ids", &self.key_ids())
+            .finish()
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum VaultCipherError {
+    UnknownKeyId(VaultCipherKeyId),
+    SealFailed,
+    DecodeFailed(base64::DecodeError),
+    NonceInvalid,
+    // Wrong key, tampered ciphertext or mismatched associated data.
+    OpenFailed,
+}
+
+impl core::fmt::Display for VaultCipherError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for VaultCipherError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let mut cipher = VaultCipher::new(1, [1; 32]);
+
+        let sealed = cipher.seal(b"EAAPAGE", b"123_456_page").unwrap();
+        assert_eq!(sealed.key_id, 1);
+        assert!(!sealed.ciphertext.contains("EAAPAGE"));
+        assert_eq!(cipher.open(&sealed, b"123_456_page").unwrap(), b"EAAPAGE");
+        assert!(matches!(
+            cipher.open(&sealed, b"123_456_user").unwrap_err(),
+            VaultCipherError::OpenFailed
+        ));
+
+        cipher.rotate(2, [2; 32]);
+        assert_eq!(cipher.current_key_id(), 2);
+        assert_eq!(cipher.open(&sealed, b"123_456_page").unwrap(), b"EAAPAGE");
+        assert_eq!(cipher.seal(b"EAAPAGE", b"123_456_page").unwrap().key_id, 2);
+
+        assert!(!cipher.remove_key(2));
+        assert!(cipher.remove_key(1));
+        assert!(matches!(
+            cipher.open(&sealed, b"123_456_page").unwrap_err(),
+            VaultCipherError::UnknownKeyId(1)
+        ));
+
+        let cipher = VaultCipher::new(2, [2; 32]).with_previous_key(1, [1; 32]);
+        assert_eq!(cipher.key_ids(), vec![1, 2]);
+        assert_eq!(cipher.open(&sealed, b"123_456_page").unwrap(), b"EAAPAGE");
+        assert_eq!(
+            format!("{cipher:?}"),
+            "VaultCipher { current_key_id: 2, key_ids: [1, 2] }"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{serde::ts_seconds_option, DateTime, Utc};
+use facebook_access_token::{
+    AccessTokenWithMetadata, KindedAccessToken, StoredAccessToken, StoredAccessTokenError,
+};
+use facebook_access_token_api::objects::DebugTokenResult;
+use serde::{Deserialize, Serialize};
+
+//
+// The plaintext of a sealed value.
+//
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VaultEntry {
+    pub token: StoredAccessToken,
+    // As returned by debug_token, for a page access token user_id is the user who granted it.
+    #[serde(default)]
+    pub app_id: Option<u64>,
+    #[serde(default)]
+    pub user_id: Option<u64>,
+    #[serde(default)]
+    pub expires_at_is_estimated: bool,
+    #[serde(default, with = "ts_seconds_option")]
+    pub issued_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "ts_seconds_option")]
+    pub data_access_expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    // None until the entry is validated with debug_token.
+    #[serde(default)]
+    pub is_valid: Option<bool>,
+    #[serde(default, with = "ts_seconds_option")]
+    pub validated_at: Option<DateTime<Utc>>,
+}
+
+impl VaultEntry {
+    pub fn new<T: KindedAccessToken>(t: &AccessTokenWithMetadata<T>) -> Self {
+        Self {
+            token: StoredAccessToken::from_access_token_with_metadata(t),
+            app_id: t.app_id,
+            user_id: t.user_id,
+            expires_at_is_estimated: t.expires_at_is_estimated,
+            issued_at: t.issued_at,
+            data_access_expires_at: t.data_access_expires_at,
+            scopes: t.scopes.to_owned(),
+            is_valid: None,
+            validated_at: None,
+        }
+    }
+
+    pub fn to_access_token_with_metadata<T: KindedAccessToken>(
+        &self,
+    ) -> Result<AccessTokenWithMetadata<T>, StoredAccessTokenError> {
+        let mut t = self.token.to_access_token_with_metadata::<T>()?;
+        t.app_id = self.app_id;
+        t.user_id = self.user_id;
+        t.expires_at_is_estimated = self.expires_at_is_estimated;
+        t.issued_at = self.issued_at;
+        t.data_access_expires_at = self.data_access_expires_at;
+        t.scopes = self.scopes.to_owned();
+        Ok(t)
+    }
+
+    // The expiry metadata is only replaced when debug_token returns it, e.g. not for invalid tokens.
+    pub fn apply_debug_token_result(&mut self, result: &DebugTokenResult, now: DateTime<Utc>) {
+        self.is_valid = Some(result.is_valid);
+        self.validated_at = Some(now);
+
+        if result.type_extra.is_none() {
+            return;
+        }
+
+        let t = result.to_access_token_with_metadata(());
+        self.app_id = t.app_id.or(self.app_id);
+        self.user_id = t.user_id.or(self.user_id);
+        self.token.expires_at = t.expires_at;
+        self.expires_at_is_estimated = false;
+        self.issued_at = t.issued_at.or(self.issued_at);
+        self.data_access_expires_at = t.data_access_expires_at;
+        self.scopes = t.scopes;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+    use facebook_access_token::{LongLivedUserAccessToken, PageAccessToken};
+
+    #[test]
+    fn test_apply_debug_token_result() {
+        let now = Utc.timestamp_opt(1668750788, 0).unwrap();
+
+        let t = AccessTokenWithMetadata::with_expires_in(
+            LongLivedUserAccessToken::from("EAAUSER"),
+            now,
+            None,
+        );
+        let mut entry = VaultEntry::new(&t);
+        assert!(entry.expires_at_is_estimated);
+        assert_eq!(entry.to_access_token_with_metadata().unwrap(), t);
+        assert!(entry
+            .to_access_token_with_metadata::<PageAccessToken>()
+            .is_err());
+
+        let result = serde_json::from_str::<DebugTokenResult>(
+            r#"{"app_id":"257422819769992","type":"USER","application":"demo","data_access_expires_at":1676858291,"expires_at":1669086000,"is_valid":true,"issued_at":1668750788,"scopes":["public_profile"],"user_id":"123"}"#,
+        )
+        .unwrap();
+        entry.apply_debug_token_result(&result, now);
+        assert_eq!(entry.is_valid, Some(true));
+        assert_eq!(entry.validated_at, Some(now));
+        assert_eq!(entry.app_id, Some(257422819769992));
+        assert_eq!(entry.user_id, Some(123));
+        assert_eq!(
+            entry.token.expires_at,
+            Utc.timestamp_opt(1669086000, 0).single()
+        );
+        assert!(!entry.expires_at_is_estimated);
+        assert_eq!(
+            entry.data_access_expires_at,
+            Utc.timestamp_opt(1676858291, 0).single()
+        );
+        assert_eq!(entry.scopes, vec!["public_profile".to_owned()]);
+
+        let result = serde_json::from_str::<DebugTokenResult>(
+            r#"{"error":{"code":190,"message":"Invalid OAuth access token signature."},"is_valid":false,"scopes":[]}"#,
+        )
+        .unwrap();
+        entry.apply_debug_token_result(&result, now);
+        assert_eq!(entry.is_valid, Some(false));
+        assert_eq!(
+            entry.token.expires_at,
+            Utc.timestamp_opt(1669086000, 0).single()
+        );
+        assert_eq!(entry.scopes, vec!["public_profile".to_owned()]);
+    }
+}
@@ -0,0 +1,110 @@
+use core::str::FromStr;
+
+use facebook_access_token::{AccessTokenKind, KindedAccessToken};
+use serde::{Deserialize, Serialize};
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VaultKey {
+    pub app_id: u64,
+    // The user, page or system user id the token belongs to.
+    pub subject_id: u64,
+    pub kind: AccessTokenKind,
+}
+
+impl VaultKey {
+    pub fn new(app_id: u64, subject_id: u64, kind: AccessTokenKind) -> Self {
+        Self {
+            app_id,
+            subject_id,
+            kind,
+        }
+    }
+
+    pub fn of<T: KindedAccessToken>(app_id: u64, subject_id: u64) -> Self {
+        Self::new(app_id, subject_id, T::KIND)
+    }
+}
+
+// `{app_id}_{subject_id}_{kind}`, e.g. `123_456_page`.
+// Also used as the associated data when sealing, so a sealed value can't be moved to another key.
+impl core::fmt::Display for VaultKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}_{}_{}",
+            self.app_id,
+            self.subject_id,
+            self.kind.as_str()
+        )
+    }
+}
+
+impl FromStr for VaultKey {
+    type Err = VaultKeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.splitn(3, '_');
+        let app_id = split
+            .next()
+            .and_then(|x| x.parse::<u64>().ok())
+            .ok_or(VaultKeyParseError::AppIdInvalid)?;
+        let subject_id = split
+            .next()
+            .and_then(|x| x.parse::<u64>().ok())
+            .ok_or(VaultKeyParseError::SubjectIdInvalid)?;
+        let kind = split
+            .next()
+            .and_then(AccessTokenKind::from_str_opt)
+            .ok_or(VaultKeyParseError::KindInvalid)?;
+
+        Ok(Self::new(app_id, subject_id, kind))
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultKeyParseError {
+    AppIdInvalid,
+    SubjectIdInvalid,
+    KindInvalid,
+}
+
+impl core::fmt::Display for VaultKeyParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for VaultKeyParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use facebook_access_token::{LongLivedInstagramUserAccessToken, PageAccessToken};
+
+    #[test]
+    fn test_to_string_and_from_str() {
+        let key = VaultKey::of::<PageAccessToken>(123, 456);
+        assert_eq!(key.to_string(), "123_456_page");
+        assert_eq!("123_456_page".parse::<VaultKey>().unwrap(), key);
+
+        let key = VaultKey::of::<LongLivedInstagramUserAccessToken>(123, 456);
+        assert_eq!(key.to_string(), "123_456_long_lived_instagram_user");
+        assert_eq!(key.to_string().parse::<VaultKey>().unwrap(), key);
+
+        assert_eq!(
+            "x_456_page".parse::<VaultKey>().unwrap_err(),
+            VaultKeyParseError::AppIdInvalid
+        );
+        assert_eq!(
+            "123_456".parse::<VaultKey>().unwrap_err(),
+            VaultKeyParseError::KindInvalid
+        );
+        assert_eq!(
+            "123_456_foo".parse::<VaultKey>().unwrap_err(),
+            VaultKeyParseError::KindInvalid
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! Encrypted at-rest storage for access tokens.
+//!
+//! Entries are looked up by (app_id, subject_id, kind) and sealed with AES-256-GCM,
+//! every sealed value records the id of the key it was sealed with, so keys can be rotated.
+
+pub use facebook_access_token;
+
+pub mod backend;
+pub mod cipher;
+pub mod entry;
+pub mod key;
+pub mod vault;
+
+pub use backend::{FileVaultBackend, FileVaultBackendError, MemoryVaultBackend, VaultBackend};
+pub use cipher::{
+    SealedValue, VaultCipher, VaultCipherError, VaultCipherKeyId, VAULT_CIPHER_KEY_LEN,
+};
+pub use entry::VaultEntry;
+pub use key::{VaultKey, VaultKeyParseError};
+pub use vault::{TokenVault, TokenVaultError};
@@ -0,0 +1,336 @@
+use chrono::{DateTime, Utc};
+use facebook_access_token::{
+    AccessTokenKind, AccessTokenWithMetadata, KindedAccessToken, StoredAccessTokenError,
+};
+use facebook_access_token_api::objects::DebugTokenResult;
+
+use crate::{
+    backend::VaultBackend,
+    cipher::{VaultCipher, VaultCipherKeyId, VAULT_CIPHER_KEY_LEN},
+    SealedValue, VaultCipherError, VaultEntry, VaultKey,
+};
+
+//
+//
+//
+#[derive(Debug)]
+pub struct TokenVault<B> {
+    backend: B,
+    cipher: VaultCipher,
+}
+
+impl<B> TokenVault<B>
+where
+    B: VaultBackend + Send + Sync,
+{
+    pub fn new(backend: B, cipher: VaultCipher) -> Self {
+        Self { backend, cipher }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn cipher(&self) -> &VaultCipher {
+        &self.cipher
+    }
+
+    // Existing entries stay readable, call rekey_all to re-seal them with the new key.
+    pub fn rotate_key(&mut self, key_id: VaultCipherKeyId, key: [u8; VAULT_CIPHER_KEY_LEN]) {
+        self.cipher.rotate(key_id, key)
+    }
+
+    pub async fn put<T: KindedAccessToken>(
+        &self,
+        app_id: u64,
+        subject_id: u64,
+        token: &AccessTokenWithMetadata<T>,
+    ) -> Result<VaultKey, TokenVaultError<B::Error>> {
+        let key = VaultKey::of::<T>(app_id, subject_id);
+        self.put_entry(&key, &VaultEntry::new(token)).await?;
+        Ok(key)
+    }
+
+    // app_id falls back to the key's, user_id to the key's subject_id only for user and system user tokens.
+    pub async fn get<T: KindedAccessToken>(
+        &self,
+        app_id: u64,
+        subject_id: u64,
+    ) -> Result<Option<AccessTokenWithMetadata<T>>, TokenVaultError<B::Error>> {
+        let key = VaultKey::of::<T>(app_id, subject_id);
+        match self.get_entry(&key).await? {
+            Some(entry) => {
+                let mut t = entry
+                    .to_access_token_with_metadata::<T>()
+                    .map_err(TokenVaultError::StoredAccessTokenInvalid)?;
+                t.app_id = t.app_id.or(Some(app_id));
+                if matches!(
+                    T::KIND,
+                    AccessTokenKind::LongLivedUser
+                        | AccessTokenKind::ShortLivedUser
+                        | AccessTokenKind::User
+                        | AccessTokenKind::UserSessionInfo
+                        | AccessTokenKind::SystemUser
+                ) {
+                    t.user_id = t.user_id.or(Some(subject_id));
+                }
+                Ok(Some(t))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn put_entry(
+        &self,
+        key: &VaultKey,
+        entry: &VaultEntry,
+    ) -> Result<(), TokenVaultError<B::Error>> {
+        if entry.token.kind != key.kind {
+            return Err(TokenVaultError::StoredAccessTokenInvalid(
+                StoredAccessTokenError::KindMismatch {
+                    expected: key.kind,
+                    found: entry.token.kind,
+                },
+            ));
+        }
+
+        let value = self.seal(key, entry)?;
+        self.backend
+            .put(key, value)
+            .await
+            .map_err(TokenVaultError::BackendFailed)
+    }
+
+    pub async fn get_entry(
+        &self,
+        key: &VaultKey,
+    ) -> Result<Option<VaultEntry>, TokenVaultError<B::Error>> {
+        match self
+            .backend
+            .get(key)
+            .await
+            .map_err(TokenVaultError::BackendFailed)?
+        {
+            Some(value) => Ok(Some(self.open(key, &value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn remove(&self, key: &VaultKey) -> Result<(), TokenVaultError<B::Error>> {
+        self.backend
+            .remove(key)
+            .await
+            .map_err(TokenVaultError::BackendFailed)
+    }
+
+    pub async fn keys(&self) -> Result<Vec<VaultKey>, TokenVaultError<B::Error>> {
+        self.backend
+            .keys()
+            .await
+            .map_err(TokenVaultError::BackendFailed)
+    }
+
+    // result is from debug_token for the entry's token.
+    // Returns None when there is no entry for key.
+    pub async fn update_from_debug_token_result(
+        &self,
+        key: &VaultKey,
+        result: &DebugTokenResult,
+        now: DateTime<Utc>,
+    ) -> Result<Option<VaultEntry>, TokenVaultError<B::Error>> {
+        let mut entry = match self.get_entry(key).await? {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        entry.apply_debug_token_result(result, now);
+        self.put_entry(key, &entry).await?;
+
+        Ok(Some(entry))
+    }
+
+    // Re-seals entries sealed with a previous key, returns how many were re-sealed.
+    pub async fn rekey_all(&self) -> Result<usize, TokenVaultError<B::Error>> {
+        let mut n = 0;
+        for key in self.keys().await? {
+            let value = match self
+                .backend
+                .get(&key)
+                .await
+                .map_err(TokenVaultError::BackendFailed)?
+            {
+                Some(value) if value.key_id != self.cipher.current_key_id() => value,
+                _ => continue,
+            };
+
+            let entry = self.open(&key, &value)?;
+            self.put_entry(&key, &entry).await?;
+            n += 1;
+        }
+
+        Ok(n)
+    }
+
+    fn seal(
+        &self,
+        key: &VaultKey,
+        entry: &VaultEntry,
+    ) -> Result<SealedValue, TokenVaultError<B::Error>> {
+        let plaintext = serde_json::to_vec(entry).map_err(TokenVaultError::SerEntryFailed)?;
+        self.cipher
+            .seal(&plaintext, key.to_string().as_bytes())
+            .map_err(TokenVaultError::CipherFailed)
+    }
+
+    fn open(
+        &self,
+        key: &VaultKey,
+        value: &SealedValue,
+    ) -> Result<VaultEntry, TokenVaultError<B::Error>> {
+        let plaintext = self
+            .cipher
+            .open(value, key.to_string().as_bytes())
+            .map_err(TokenVaultError::CipherFailed)?;
+        serde_json::from_slice(&plaintext).map_err(TokenVaultError::DeEntryFailed)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum TokenVaultError<E> {
+    BackendFailed(E),
+    CipherFailed(VaultCipherError),
+    SerEntryFailed(serde_json::Error),
+    DeEntryFailed(serde_json::Error),
+    StoredAccessTokenInvalid(StoredAccessTokenError),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for TokenVaultError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl<E: core::fmt::Debug> std::error::Error for TokenVaultError<E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+    use facebook_access_token::{PageAccessToken, UserAccessToken};
+    use futures_lite::future::block_on;
+
+    use crate::MemoryVaultBackend;
+
+    #[test]
+    fn test_put_get_update() {
+        block_on(async {
+            let mut vault =
+                TokenVault::new(MemoryVaultBackend::new(), VaultCipher::new(1, [1; 32]));
+
+            let t = AccessTokenWithMetadata::new(PageAccessToken::from("EAAPAGE"));
+            let key = vault.put(123, 456, &t).await.unwrap();
+            assert_eq!(key, VaultKey::new(123, 456, AccessTokenKind::Page));
+
+            let t = vault
+                .get::<PageAccessToken>(123, 456)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(t.token, PageAccessToken::from("EAAPAGE"));
+            assert_eq!(t.app_id, Some(123));
+            assert_eq!(t.user_id, None);
+            assert!(vault
+                .get::<UserAccessToken>(123, 456)
+                .await
+                .unwrap()
+                .is_none());
+
+            vault
+                .put(
+                    123,
+                    111,
+                    &AccessTokenWithMetadata::new(UserAccessToken::from("EAAUSER")),
+                )
+                .await
+                .unwrap();
+            let t = vault
+                .get::<UserAccessToken>(123, 111)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(t.user_id, Some(111));
+            vault
+                .remove(&VaultKey::of::<UserAccessToken>(123, 111))
+                .await
+                .unwrap();
+            assert!(vault
+                .get::<PageAccessToken>(123, 789)
+                .await
+                .unwrap()
+                .is_none());
+
+            // A sealed value moved to another key can't be opened.
+            let value = vault.backend().get(&key).await.unwrap().unwrap();
+            let other_key = VaultKey::new(123, 789, AccessTokenKind::Page);
+            vault.backend().put(&other_key, value).await.unwrap();
+            assert!(matches!(
+                vault.get_entry(&other_key).await.unwrap_err(),
+                TokenVaultError::CipherFailed(VaultCipherError::OpenFailed)
+            ));
+            vault.remove(&other_key).await.unwrap();
+
+            assert!(matches!(
+                vault
+                    .put_entry(
+                        &VaultKey::new(123, 456, AccessTokenKind::User),
+                        &vault.get_entry(&key).await.unwrap().unwrap()
+                    )
+                    .await
+                    .unwrap_err(),
+                TokenVaultError::StoredAccessTokenInvalid(
+                    StoredAccessTokenError::KindMismatch { .. }
+                )
+            ));
+
+            //
+            let now = Utc.timestamp_opt(1668750788, 0).unwrap();
+            let result = serde_json::from_str::<DebugTokenResult>(
+                r#"{"app_id":"123","type":"PAGE","application":"demo","data_access_expires_at":1676858291,"expires_at":1669086000,"is_valid":true,"profile_id":"456","scopes":["pages_show_list"],"user_id":"789"}"#,
+            )
+            .unwrap();
+            let entry = vault
+                .update_from_debug_token_result(&key, &result, now)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(entry.is_valid, Some(true));
+            let t = vault
+                .get::<PageAccessToken>(123, 456)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(t.expires_at, Utc.timestamp_opt(1669086000, 0).single());
+            assert_eq!(t.scopes, vec!["pages_show_list".to_owned()]);
+            // user_id is the user from debug_token, not the page id of the key.
+            assert_eq!(t.app_id, Some(123));
+            assert_eq!(t.user_id, Some(789));
+            assert!(vault
+                .update_from_debug_token_result(&other_key, &result, now)
+                .await
+                .unwrap()
+                .is_none());
+
+            //
+            vault.rotate_key(2, [2; 32]);
+            assert_eq!(
+                vault.get::<PageAccessToken>(123, 456).await.unwrap(),
+                Some(t)
+            );
+            assert_eq!(vault.rekey_all().await.unwrap(), 1);
+            assert_eq!(vault.backend().get(&key).await.unwrap().unwrap().key_id, 2);
+            assert_eq!(vault.rekey_all().await.unwrap(), 0);
+        })
+    }
+}
@@ -0,0 +1,107 @@
+//! [Ref](https://developers.facebook.com/docs/graph-api/securing-requests#appsecret_proof)
+
+use hmac::{Hmac, Mac as _};
+use sha2::Sha256;
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppSecretProof {
+    pub proof: String,
+    pub time: Option<u64>,
+}
+
+impl AppSecretProof {
+    pub fn new(access_token: impl AsRef<str>, app_secret: impl AsRef<str>) -> Self {
+        Self {
+            proof: hmac_sha256_hex(app_secret.as_ref(), access_token.as_ref()),
+            time: None,
+        }
+    }
+
+    // time is the unix timestamp sent as appsecret_time, it must be within 5 minutes of the server time.
+    pub fn with_time(
+        access_token: impl AsRef<str>,
+        app_secret: impl AsRef<str>,
+        time: u64,
+    ) -> Self {
+        Self {
+            proof: hmac_sha256_hex(
+                app_secret.as_ref(),
+                format!("{}|{}", access_token.as_ref(), time).as_str(),
+            ),
+            time: Some(time),
+        }
+    }
+
+    pub fn with_optional_time(
+        access_token: impl AsRef<str>,
+        app_secret: impl AsRef<str>,
+        time: Option<u64>,
+    ) -> Self {
+        match time {
+            Some(time) => Self::with_time(access_token, app_secret, time),
+            None => Self::new(access_token, app_secret),
+        }
+    }
+
+    // The appsecret_proof and appsecret_time pairs, for url.query_pairs_mut().extend_pairs or a form body.
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![("appsecret_proof", self.proof.to_owned())];
+        if let Some(time) = self.time {
+            pairs.push(("appsecret_time", time.to_string()));
+        }
+        pairs
+    }
+}
+
+fn hmac_sha256_hex(key: &str, data: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(data.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_secret_proof() {
+        assert_eq!(
+            AppSecretProof::new("ACCESS_TOKEN", "APP_SECRET"),
+            AppSecretProof {
+                proof: "a727796e1b4e9053916f82f7a0b90f240862b289bb3c9ac5ff6e2231e18a491c".into(),
+                time: None,
+            }
+        );
+        assert_eq!(
+            AppSecretProof::with_time("ACCESS_TOKEN", "APP_SECRET", 1672531200),
+            AppSecretProof {
+                proof: "61eb6bf9d00cce03ecea8b29edc32d8dec9d3d4a2608f357cc9734efd63fa581".into(),
+                time: Some(1672531200),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pairs() {
+        assert_eq!(
+            AppSecretProof::with_optional_time("ACCESS_TOKEN", "APP_SECRET", None).pairs(),
+            vec![(
+                "appsecret_proof",
+                "a727796e1b4e9053916f82f7a0b90f240862b289bb3c9ac5ff6e2231e18a491c".to_owned()
+            )]
+        );
+        assert_eq!(
+            AppSecretProof::with_optional_time("ACCESS_TOKEN", "APP_SECRET", Some(1672531200))
+                .pairs(),
+            vec![
+                (
+                    "appsecret_proof",
+                    "61eb6bf9d00cce03ecea8b29edc32d8dec9d3d4a2608f357cc9734efd63fa581".to_owned()
+                ),
+                ("appsecret_time", "1672531200".to_owned())
+            ]
+        );
+    }
+}
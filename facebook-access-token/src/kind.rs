@@ -0,0 +1,126 @@
+use crate::{
+    AppAccessToken, ClientAccessToken, LongLivedInstagramUserAccessToken, LongLivedUserAccessToken,
+    OidcIdToken, PageAccessToken, PageSessionInfoAccessToken, ShortLivedInstagramUserAccessToken,
+    ShortLivedUserAccessToken, SystemUserAccessToken, UserAccessToken, UserSessionInfoAccessToken,
+};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum AccessTokenKind {
+    LongLivedUser,
+    ShortLivedUser,
+    User,
+    App,
+    Page,
+    SystemUser,
+    Client,
+    UserSessionInfo,
+    PageSessionInfo,
+    OidcId,
+    LongLivedInstagramUser,
+    ShortLivedInstagramUser,
+}
+
+impl AccessTokenKind {
+    pub const ALL: &'static [Self] = &[
+        Self::LongLivedUser,
+        Self::ShortLivedUser,
+        Self::User,
+        Self::App,
+        Self::Page,
+        Self::SystemUser,
+        Self::Client,
+        Self::UserSessionInfo,
+        Self::PageSessionInfo,
+        Self::OidcId,
+        Self::LongLivedInstagramUser,
+        Self::ShortLivedInstagramUser,
+    ];
+
+    // The same as the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LongLivedUser => "long_lived_user",
+            Self::ShortLivedUser => "short_lived_user",
+            Self::User => "user",
+            Self::App => "app",
+            Self::Page => "page",
+            Self::SystemUser => "system_user",
+            Self::Client => "client",
+            Self::UserSessionInfo => "user_session_info",
+            Self::PageSessionInfo => "page_session_info",
+            Self::OidcId => "oidc_id",
+            Self::LongLivedInstagramUser => "long_lived_instagram_user",
+            Self::ShortLivedInstagramUser => "short_lived_instagram_user",
+        }
+    }
+
+    pub fn from_str_opt(s: &str) -> Option<Self> {
+        Self::ALL.iter().find(|x| x.as_str() == s).copied()
+    }
+}
+
+impl core::fmt::Display for AccessTokenKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+//
+pub trait KindedAccessToken: From<String> {
+    const KIND: AccessTokenKind;
+
+    fn reveal(&self) -> &str;
+}
+
+macro_rules! impl_kinded_access_token {
+    ($( $ty:ty => $kind:ident ),* $(,)?) => {
+        $(
+            impl KindedAccessToken for $ty {
+                const KIND: AccessTokenKind = AccessTokenKind::$kind;
+
+                fn reveal(&self) -> &str {
+                    self.inner()
+                }
+            }
+        )*
+    };
+}
+
+impl_kinded_access_token! {
+    LongLivedUserAccessToken => LongLivedUser,
+    ShortLivedUserAccessToken => ShortLivedUser,
+    UserAccessToken => User,
+    AppAccessToken => App,
+    PageAccessToken => Page,
+    SystemUserAccessToken => SystemUser,
+    ClientAccessToken => Client,
+    UserSessionInfoAccessToken => UserSessionInfo,
+    PageSessionInfoAccessToken => PageSessionInfo,
+    OidcIdToken => OidcId,
+    LongLivedInstagramUserAccessToken => LongLivedInstagramUser,
+    ShortLivedInstagramUserAccessToken => ShortLivedInstagramUser,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_str() {
+        for kind in AccessTokenKind::ALL {
+            assert_eq!(AccessTokenKind::from_str_opt(kind.as_str()), Some(*kind));
+            #[cfg(feature = "serde")]
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::Value::from(kind.as_str())
+            );
+        }
+        assert_eq!(AccessTokenKind::from_str_opt("foo"), None);
+    }
+}
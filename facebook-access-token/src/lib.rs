@@ -2,6 +2,33 @@
 
 use core::time::Duration;
 
+//
+pub mod app_secret_proof;
+
+pub use app_secret_proof::AppSecretProof;
+
+pub mod classify;
+
+pub use classify::{classify, AccessTokenGuess, Classification, ClassifyConfidence};
+
+pub mod kind;
+
+pub use kind::{AccessTokenKind, KindedAccessToken};
+
+pub mod secret;
+
+use secret::wrapping_secret_string;
+
+pub mod with_metadata;
+
+pub use with_metadata::{AccessTokenLifetime, AccessTokenWithMetadata};
+
+#[cfg(feature = "serde")]
+pub mod stored;
+
+#[cfg(feature = "serde")]
+pub use stored::{StoredAccessToken, StoredAccessTokenError, STORED_ACCESS_TOKEN_VERSION};
+
 //
 //
 //
@@ -10,18 +37,18 @@ pub const LONG_LIVED_USER_ACCESS_TOKEN_LIFETIME: Duration = Duration::from_secs(
 pub const SHORT_LIVED_USER_ACCESS_TOKEN_LIFETIME_MIN: Duration = Duration::from_secs(3600);
 pub const SHORT_LIVED_USER_ACCESS_TOKEN_LIFETIME_MAX: Duration = Duration::from_secs(3600 * 2);
 
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct LongLivedUserAccessToken(String);
 }
 
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct ShortLivedUserAccessToken(String);
 }
 
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct UserAccessToken(String);
 }
 
@@ -49,11 +76,27 @@ impl From<&ShortLivedUserAccessToken> for UserAccessToken {
     }
 }
 
+/*
+https://developers.facebook.com/docs/facebook-login/guides/access-tokens/get-long-lived#get-a-long-lived-user-access-token-from-a-code
+The server turns a LongLivedUserAccessToken into a one-time ClientCode,
+the client redeems it without the app secret and keeps the returned MachineId for the next redeem.
+*/
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct ClientCode(String);
+}
+
+wrapping_macro::wrapping_string! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+    pub struct MachineId(String);
+}
+
 //
 //
 //
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct AppAccessToken(String);
 }
 
@@ -83,19 +126,32 @@ impl AppAccessToken {
 //
 //
 /*
-Get from https://graph.facebook.com/v15.0/me?fields=id%2Cname%2Caccounts%7Bid%2Cname%2Caccess_token%7D&access_token=YOUR_SHORT_LIVED_OR_LONG_LIVED_USER_ACCESS_TOKEN
+Get from /me/accounts, see facebook_access_token_api::endpoints::get_page_access_tokens
 PageAccessToken expires == UserAccessToken expires
+Got via a LongLivedUserAccessToken, it never expires
 */
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct PageAccessToken(String);
 }
 
 //
 //
 //
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+/*
+https://developers.facebook.com/docs/marketing-api/system-users/overview
+Belongs to a Business Manager system user, never expires unless generated with set_token_expires_in_60_days
+*/
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct SystemUserAccessToken(String);
+}
+
+//
+//
+//
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct ClientAccessToken(String);
 }
 
@@ -114,22 +170,56 @@ does not grant access to user data
 calling the debug_token endpoint to verify that it is valid
 */
 //
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct UserSessionInfoAccessToken(String);
 }
 
 //
-wrapping_macro::wrapping_string! {
-    #[derive(Debug, Clone, PartialEq, Eq)]
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
     pub struct PageSessionInfoAccessToken(String);
 }
 
+//
+//
+//
+/*
+https://developers.facebook.com/docs/facebook-login/limited-login/token
+Limited Login returns an OIDC id_token (JWT) instead of a Graph API access token.
+*/
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct OidcIdToken(String);
+}
+
+//
+//
+//
+/*
+https://developers.facebook.com/docs/instagram-basic-display-api/guides/long-lived-access-tokens
+Instagram Login tokens (IGQV...), exchanged and refreshed at graph.instagram.com
+*/
+pub const LONG_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME: Duration =
+    Duration::from_secs(3600 * 24 * 60);
+pub const SHORT_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct LongLivedInstagramUserAccessToken(String);
+}
+
+wrapping_secret_string! {
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct ShortLivedInstagramUserAccessToken(String);
+}
+
 //
 //
 //
 wrapping_macro::wrapping_int! {
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
     pub struct AccessTokenExpiresIn(usize);
 }
 
@@ -151,4 +241,18 @@ mod tests {
         assert!(AppAccessToken::from("1|").app_id_and_app_secret().is_none());
         assert!(AppAccessToken::from("|x").app_id_and_app_secret().is_none());
     }
+
+    #[test]
+    fn test_redacted_debug_and_display() {
+        let t = AppAccessToken::with_app_secret(123, "APP_SECRET");
+        assert!(!format!("{t:?}").contains("APP_SECRET"));
+        assert!(!format!("{t}").contains("APP_SECRET"));
+        assert!(format!("{t}").starts_with("123|"));
+        assert_eq!(t.reveal(), "123|APP_SECRET");
+
+        let t = UserAccessToken::from("EAABwzLixnjYBAOZBZA1234567890");
+        assert_eq!(format!("{t:?}"), r#"UserAccessToken("EAABwz...#e3a4278b")"#);
+        assert_eq!(format!("{t}"), "EAABwz...#e3a4278b");
+        assert_eq!(t.into_inner(), "EAABwzLixnjYBAOZBZA1234567890");
+    }
 }
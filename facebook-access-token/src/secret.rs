@@ -0,0 +1,141 @@
+//! Redacted Debug/Display for secret values such as access tokens.
+
+use sha2::{Digest as _, Sha256};
+
+//
+const REDACT_PREFIX_LEN: usize = 6;
+
+// Keeps the first 6 chars plus a sha256 fingerprint, e.g. `EAABwz...#1a2b3c4d`.
+// For `app_id|secret` values (AppAccessToken, ClientAccessToken) only the app_id is kept.
+pub fn redact(value: &str) -> String {
+    let fingerprint = hex::encode(&Sha256::digest(value.as_bytes())[..4]);
+
+    if let Some((app_id, _)) = value.split_once('|') {
+        return format!("{app_id}|...#{fingerprint}");
+    }
+
+    // Too short to show any prefix.
+    if value.chars().count() <= REDACT_PREFIX_LEN * 2 {
+        return format!("...#{fingerprint}");
+    }
+
+    let prefix = value.chars().take(REDACT_PREFIX_LEN).collect::<String>();
+    format!("{prefix}...#{fingerprint}")
+}
+
+//
+// Like wrapping_macro::wrapping_string, but Debug/Display are redacted.
+// Use reveal() to get the value, with the zeroize feature the value is zeroized on drop.
+// With the serde feature the value is (de)serialized as a plain string.
+macro_rules! wrapping_secret_string {
+    (
+        $( #[$meta:meta] )*
+        $pub:vis struct $name:ident(String);
+    ) => {
+        $( #[$meta] )*
+        #[cfg_attr(
+            feature = "serde",
+            derive(::serde::Serialize, ::serde::Deserialize),
+            serde(transparent)
+        )]
+        $pub struct $name(String);
+
+        impl $name {
+            pub fn from_inner(inner: String) -> Self {
+                Self(inner)
+            }
+
+            pub fn inner(&self) -> &String {
+                &self.0
+            }
+
+            pub fn into_inner(mut self) -> String {
+                ::core::mem::take(&mut self.0)
+            }
+
+            pub fn reveal(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::From<String> for $name {
+            fn from(v: String) -> Self {
+                Self(v)
+            }
+        }
+        impl ::core::convert::From<&String> for $name {
+            fn from(v: &String) -> Self {
+                Self(v.to_owned())
+            }
+        }
+        impl ::core::convert::From<&str> for $name {
+            fn from(v: &str) -> Self {
+                Self(v.into())
+            }
+        }
+        impl ::core::convert::From<Box<str>> for $name {
+            fn from(v: Box<str>) -> Self {
+                Self(v.into())
+            }
+        }
+        impl ::core::convert::From<&Box<str>> for $name {
+            fn from(v: &Box<str>) -> Self {
+                Self(v.to_string())
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = String;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&$crate::secret::redact(&self.0))
+                    .finish()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}", $crate::secret::redact(&self.0))
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = ::core::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(s.into()))
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl Drop for $name {
+            fn drop(&mut self) {
+                ::zeroize::Zeroize::zeroize(&mut self.0);
+            }
+        }
+    };
+}
+
+pub(crate) use wrapping_secret_string;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("EAABwzLixnjYBAOZBZA1234567890"),
+            "EAABwz...#e3a4278b"
+        );
+        assert_eq!(redact("123|APP_SECRET"), "123|...#a33585be");
+        assert_eq!(redact("short"), "...#f9b0078b");
+    }
+}
@@ -0,0 +1,152 @@
+//! A versioned, self-describing form for persisting access tokens.
+
+use chrono::{serde::ts_seconds_option, DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{secret::redact, AccessTokenKind, AccessTokenWithMetadata, KindedAccessToken};
+
+//
+pub const STORED_ACCESS_TOKEN_VERSION: u32 = 1;
+
+//
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct StoredAccessToken {
+    pub version: u32,
+    pub kind: AccessTokenKind,
+    pub value: String,
+    #[serde(default, with = "ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredAccessToken {
+    pub fn new<T: KindedAccessToken>(token: &T, expires_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            version: STORED_ACCESS_TOKEN_VERSION,
+            kind: T::KIND,
+            value: token.reveal().into(),
+            expires_at,
+        }
+    }
+
+    pub fn from_access_token_with_metadata<T: KindedAccessToken>(
+        t: &AccessTokenWithMetadata<T>,
+    ) -> Self {
+        Self::new(&t.token, t.expires_at)
+    }
+
+    // Fails when the version is unknown or the kind is not T::KIND.
+    pub fn to_access_token<T: KindedAccessToken>(&self) -> Result<T, StoredAccessTokenError> {
+        if self.version != STORED_ACCESS_TOKEN_VERSION {
+            return Err(StoredAccessTokenError::UnsupportedVersion(self.version));
+        }
+        if self.kind != T::KIND {
+            return Err(StoredAccessTokenError::KindMismatch {
+                expected: T::KIND,
+                found: self.kind,
+            });
+        }
+        Ok(self.value.to_owned().into())
+    }
+
+    pub fn to_access_token_with_metadata<T: KindedAccessToken>(
+        &self,
+    ) -> Result<AccessTokenWithMetadata<T>, StoredAccessTokenError> {
+        let mut t = AccessTokenWithMetadata::new(self.to_access_token::<T>()?);
+        t.expires_at = self.expires_at;
+        Ok(t)
+    }
+}
+
+impl core::fmt::Debug for StoredAccessToken {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StoredAccessToken")
+            .field("version", &self.version)
+            .field("kind", &self.kind)
+            .field("value", &redact(&self.value))
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredAccessTokenError {
+    UnsupportedVersion(u32),
+    KindMismatch {
+        expected: AccessTokenKind,
+        found: AccessTokenKind,
+    },
+}
+
+impl core::fmt::Display for StoredAccessTokenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for StoredAccessTokenError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+
+    use crate::{AccessTokenExpiresIn, PageAccessToken, UserAccessToken};
+
+    #[test]
+    fn test_round_trip() {
+        let expires_at = Utc.timestamp_opt(1673934788, 0).single();
+        let stored = StoredAccessToken::new(&PageAccessToken::from("EAAPAGE"), expires_at);
+
+        let json = serde_json::to_string(&stored).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":1,"kind":"page","value":"EAAPAGE","expires_at":1673934788}"#
+        );
+        assert!(!format!("{stored:?}").contains("EAAPAGE"));
+
+        let stored = serde_json::from_str::<StoredAccessToken>(&json).unwrap();
+        assert_eq!(
+            stored.to_access_token::<PageAccessToken>().unwrap(),
+            PageAccessToken::from("EAAPAGE")
+        );
+        let t = stored
+            .to_access_token_with_metadata::<PageAccessToken>()
+            .unwrap();
+        assert_eq!(t.expires_at, expires_at);
+
+        assert_eq!(
+            stored.to_access_token::<UserAccessToken>().unwrap_err(),
+            StoredAccessTokenError::KindMismatch {
+                expected: AccessTokenKind::User,
+                found: AccessTokenKind::Page,
+            }
+        );
+
+        let stored = serde_json::from_str::<StoredAccessToken>(
+            r#"{"version":2,"kind":"page","value":"EAAPAGE"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            stored.to_access_token::<PageAccessToken>().unwrap_err(),
+            StoredAccessTokenError::UnsupportedVersion(2)
+        );
+    }
+
+    #[test]
+    fn test_transparent() {
+        assert_eq!(
+            serde_json::to_string(&UserAccessToken::from("EAAUSER")).unwrap(),
+            r#""EAAUSER""#
+        );
+        assert_eq!(
+            serde_json::from_str::<UserAccessToken>(r#""EAAUSER""#).unwrap(),
+            UserAccessToken::from("EAAUSER")
+        );
+        assert_eq!(
+            serde_json::from_str::<AccessTokenExpiresIn>("5183944").unwrap(),
+            AccessTokenExpiresIn::from(5183944)
+        );
+    }
+}
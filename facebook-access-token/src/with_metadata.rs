@@ -0,0 +1,193 @@
+use core::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    AccessTokenExpiresIn, AppAccessToken, ClientAccessToken, LongLivedInstagramUserAccessToken,
+    LongLivedUserAccessToken, PageAccessToken, PageSessionInfoAccessToken,
+    ShortLivedInstagramUserAccessToken, ShortLivedUserAccessToken, SystemUserAccessToken,
+    UserAccessToken, UserSessionInfoAccessToken, LONG_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME,
+    LONG_LIVED_USER_ACCESS_TOKEN_LIFETIME, SHORT_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME,
+    SHORT_LIVED_USER_ACCESS_TOKEN_LIFETIME_MIN,
+};
+
+//
+//
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessTokenWithMetadata<T> {
+    pub token: T,
+    pub issued_at: Option<DateTime<Utc>>,
+    // None when the token never expires or the expiry is unknown.
+    pub expires_at: Option<DateTime<Utc>>,
+    // true when expires_at comes from estimated_lifetime instead of Facebook.
+    pub expires_at_is_estimated: bool,
+    pub data_access_expires_at: Option<DateTime<Utc>>,
+    pub scopes: Vec<String>,
+    pub app_id: Option<u64>,
+    pub user_id: Option<u64>,
+}
+
+impl<T> AccessTokenWithMetadata<T> {
+    pub fn new(token: T) -> Self {
+        Self {
+            token,
+            issued_at: None,
+            expires_at: None,
+            expires_at_is_estimated: false,
+            data_access_expires_at: None,
+            scopes: vec![],
+            app_id: None,
+            user_id: None,
+        }
+    }
+
+    pub fn with_expires_in(
+        token: T,
+        issued_at: DateTime<Utc>,
+        expires_in: Option<AccessTokenExpiresIn>,
+    ) -> Self
+    where
+        T: AccessTokenLifetime,
+    {
+        let (expires_at, expires_at_is_estimated) = match expires_in {
+            Some(expires_in) => (
+                Some(
+                    issued_at
+                        + chrono_duration(Duration::from_secs(expires_in.into_inner() as u64)),
+                ),
+                false,
+            ),
+            None => match T::estimated_lifetime() {
+                Some(lifetime) => (Some(issued_at + chrono_duration(lifetime)), true),
+                None => (None, false),
+            },
+        };
+
+        Self {
+            issued_at: Some(issued_at),
+            expires_at,
+            expires_at_is_estimated,
+            ..Self::new(token)
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at <= now,
+            None => false,
+        }
+    }
+
+    pub fn expires_within(&self, duration: Duration, now: DateTime<Utc>) -> bool {
+        match self.remaining_lifetime(now) {
+            Some(remaining_lifetime) => remaining_lifetime <= duration,
+            None => false,
+        }
+    }
+
+    // None when the token never expires or the expiry is unknown.
+    pub fn remaining_lifetime(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| (expires_at - now).to_std().unwrap_or_default())
+    }
+}
+
+fn chrono_duration(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX)
+}
+
+//
+//
+//
+pub trait AccessTokenLifetime {
+    // Used when Facebook omits expires_in.
+    fn estimated_lifetime() -> Option<Duration> {
+        None
+    }
+}
+
+impl AccessTokenLifetime for LongLivedUserAccessToken {
+    fn estimated_lifetime() -> Option<Duration> {
+        Some(LONG_LIVED_USER_ACCESS_TOKEN_LIFETIME)
+    }
+}
+
+impl AccessTokenLifetime for ShortLivedUserAccessToken {
+    fn estimated_lifetime() -> Option<Duration> {
+        Some(SHORT_LIVED_USER_ACCESS_TOKEN_LIFETIME_MIN)
+    }
+}
+
+impl AccessTokenLifetime for LongLivedInstagramUserAccessToken {
+    fn estimated_lifetime() -> Option<Duration> {
+        Some(LONG_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME)
+    }
+}
+
+impl AccessTokenLifetime for ShortLivedInstagramUserAccessToken {
+    fn estimated_lifetime() -> Option<Duration> {
+        Some(SHORT_LIVED_INSTAGRAM_USER_ACCESS_TOKEN_LIFETIME)
+    }
+}
+
+impl AccessTokenLifetime for UserAccessToken {}
+impl AccessTokenLifetime for AppAccessToken {}
+impl AccessTokenLifetime for PageAccessToken {}
+impl AccessTokenLifetime for SystemUserAccessToken {}
+impl AccessTokenLifetime for ClientAccessToken {}
+impl AccessTokenLifetime for UserSessionInfoAccessToken {}
+impl AccessTokenLifetime for PageSessionInfoAccessToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone as _;
+
+    #[test]
+    fn test_with_expires_in() {
+        let issued_at = Utc.timestamp_opt(1668750788, 0).unwrap();
+
+        let t = AccessTokenWithMetadata::with_expires_in(
+            LongLivedUserAccessToken::from("x"),
+            issued_at,
+            Some(3600.into()),
+        );
+        assert_eq!(
+            t.expires_at,
+            Utc.timestamp_opt(1668750788 + 3600, 0).single()
+        );
+        assert!(!t.expires_at_is_estimated);
+        assert!(!t.is_expired(issued_at));
+        assert!(t.is_expired(issued_at + chrono::Duration::seconds(3600)));
+        assert!(!t.expires_within(Duration::from_secs(3599), issued_at));
+        assert!(t.expires_within(Duration::from_secs(3600), issued_at));
+        assert_eq!(
+            t.remaining_lifetime(issued_at),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(
+            t.remaining_lifetime(issued_at + chrono::Duration::seconds(7200)),
+            Some(Duration::ZERO)
+        );
+
+        let t = AccessTokenWithMetadata::with_expires_in(
+            LongLivedUserAccessToken::from("x"),
+            issued_at,
+            None,
+        );
+        assert_eq!(
+            t.expires_at,
+            Utc.timestamp_opt(1668750788 + 3600 * 24 * 60, 0).single()
+        );
+        assert!(t.expires_at_is_estimated);
+
+        let t =
+            AccessTokenWithMetadata::with_expires_in(PageAccessToken::from("x"), issued_at, None);
+        assert!(t.expires_at.is_none());
+        assert!(!t.is_expired(issued_at + chrono::Duration::days(365 * 10)));
+        assert!(!t.expires_within(Duration::from_secs(3600), issued_at));
+        assert!(t.remaining_lifetime(issued_at).is_none());
+    }
+}
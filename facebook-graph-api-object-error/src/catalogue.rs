@@ -0,0 +1,228 @@
+//! Documented Graph API, Instagram and Marketing API error codes and subcodes.
+//!
+//! https://developers.facebook.com/docs/graph-api/guides/error-handling
+//! https://developers.facebook.com/docs/graph-api/overview/rate-limiting
+//! https://developers.facebook.com/docs/instagram-api/reference/error-codes
+//! https://developers.facebook.com/docs/marketing-api/error-reference
+
+use core::ops::RangeInclusive;
+
+use crate::KnownErrorCase;
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    // Facebook side problems, usually temporary.
+    Service,
+    RateLimit,
+    AccessToken,
+    Permission,
+    InvalidParameter,
+    Deprecated,
+    // Blocked or restricted for policy reasons.
+    Policy,
+    Duplicate,
+    InstagramPublishing,
+    Messaging,
+    Ads,
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorCatalogueEntry {
+    // None matches any code.
+    pub code: Option<RangeInclusive<i32>>,
+    // None matches any subcode, including none.
+    pub subcode: Option<RangeInclusive<i32>>,
+    pub category: ErrorCategory,
+    // The same request may succeed later.
+    pub retry: bool,
+    // The user has to do something, e.g. log in again or grant a permission.
+    pub user_action: bool,
+    pub known_error_case: Option<KnownErrorCase>,
+    pub description: &'static str,
+}
+
+impl ErrorCatalogueEntry {
+    pub fn matches(&self, code: i32, subcode: Option<i32>) -> bool {
+        if let Some(x) = &self.code {
+            if !x.contains(&code) {
+                return false;
+            }
+        }
+        match (&self.subcode, subcode) {
+            (Some(x), Some(subcode)) => x.contains(&subcode),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+// Entries with a subcode are tried first, in table order, then entries without.
+pub fn lookup(code: i32, subcode: Option<i32>) -> Option<&'static ErrorCatalogueEntry> {
+    ERROR_CATALOGUE
+        .iter()
+        .filter(|x| x.subcode.is_some())
+        .find(|x| x.matches(code, subcode))
+        .or_else(|| {
+            ERROR_CATALOGUE
+                .iter()
+                .filter(|x| x.subcode.is_none())
+                .find(|x| x.matches(code, subcode))
+        })
+}
+
+//
+const fn c(code: i32) -> Option<RangeInclusive<i32>> {
+    Some(code..=code)
+}
+
+const fn r(start: i32, end: i32) -> Option<RangeInclusive<i32>> {
+    Some(start..=end)
+}
+
+const ANY: Option<RangeInclusive<i32>> = None;
+
+macro_rules! known_error_case {
+    () => {
+        None
+    };
+    ($name:ident) => {
+        Some(KnownErrorCase::$name)
+    };
+}
+
+macro_rules! error_catalogue {
+    ($( [$code:expr, $subcode:expr, $category:ident, retry: $retry:literal, user_action: $user_action:literal, $($known_error_case:ident)?, $description:literal] ),* $(,)?) => {
+        pub static ERROR_CATALOGUE: &[ErrorCatalogueEntry] = &[
+            $(
+                ErrorCatalogueEntry {
+                    code: $code,
+                    subcode: $subcode,
+                    category: ErrorCategory::$category,
+                    retry: $retry,
+                    user_action: $user_action,
+                    known_error_case: known_error_case!($($known_error_case)?),
+                    description: $description,
+                },
+            )*
+        ];
+    };
+}
+
+error_catalogue! {
+    // Graph API
+    [c(1), ANY, Service, retry: true, user_action: false, RetryLater, "API Unknown, possibly a temporary issue due to downtime"],
+    [c(2), ANY, Service, retry: true, user_action: false, RetryLater, "API Service, temporary issue due to downtime"],
+    [c(3), ANY, Permission, retry: false, user_action: false, , "API Method, capability or permissions issue"],
+    [c(4), ANY, RateLimit, retry: true, user_action: false, ApiTooManyCalls, "API Too Many Calls, the app reached its rate limit"],
+    [c(10), ANY, Permission, retry: false, user_action: true, PermissionNotGrantedOrRemoved, "API Permission Denied, permission not granted or removed"],
+    [c(12), ANY, Deprecated, retry: false, user_action: false, , "The requested field or endpoint is deprecated"],
+    [c(17), ANY, RateLimit, retry: true, user_action: false, ApiUserTooManyCalls, "API User Too Many Calls, the user reached the rate limit"],
+    [c(32), ANY, RateLimit, retry: true, user_action: false, PageTooManyCalls, "Page request limit reached"],
+    [c(100), ANY, InvalidParameter, retry: false, user_action: false, , "Invalid parameter"],
+    [c(102), ANY, AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "API Session, the login status or access token has expired, been revoked or is invalid"],
+    [c(190), c(458), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The user has not authorized the app"],
+    [c(190), c(459), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The user is checkpointed and must log in at www.facebook.com"],
+    [c(190), c(460), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The session has been invalidated, e.g. the user changed their password"],
+    [c(190), c(463), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The access token has expired"],
+    [c(190), c(464), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The user is not confirmed and must log in at www.facebook.com"],
+    [c(190), c(467), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The access token is invalid"],
+    [c(190), c(492), AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "Invalid session, e.g. the user's role on the Page changed"],
+    [c(190), ANY, AccessToken, retry: false, user_action: true, AccessTokenExpiredOrRevokedOrInvalid, "The access token has expired, been revoked or is invalid"],
+    [r(200, 299), ANY, Permission, retry: false, user_action: true, PermissionNotGrantedOrRemoved, "API Permission, permission not granted or removed"],
+    [c(341), ANY, RateLimit, retry: true, user_action: false, , "Application limit reached, temporary issue due to downtime or throttling"],
+    [c(368), ANY, Policy, retry: false, user_action: true, , "Temporarily blocked for policies violations"],
+    [c(506), ANY, Duplicate, retry: false, user_action: false, , "Duplicate post"],
+    [c(613), ANY, RateLimit, retry: true, user_action: false, CustomRateLimitReached, "Calls to this API have exceeded the rate limit"],
+    [c(803), ANY, InvalidParameter, retry: false, user_action: false, , "Some of the aliases requested do not exist"],
+    [c(2635), ANY, Deprecated, retry: false, user_action: false, , "The API version is deprecated"],
+    // Business use case rate limits
+    [r(80000, 80014), ANY, RateLimit, retry: true, user_action: false, BusinessUseCaseTooManyCalls, "Business use case rate limit reached, e.g. 80001 Pages, 80002 Instagram, 80004 Ads Management"],
+    // Messenger
+    [c(551), ANY, Messaging, retry: false, user_action: false, , "The person is not available right now"],
+    [c(1200), ANY, Messaging, retry: true, user_action: false, , "Temporary send message failure"],
+    [c(10), c(2018278), Messaging, retry: false, user_action: false, , "The message is sent outside of the allowed window"],
+    // Marketing API
+    [c(17), c(2446079), RateLimit, retry: true, user_action: false, ApiUserTooManyCalls, "User request limit reached for the ad account"],
+    [c(1487390), ANY, Ads, retry: false, user_action: false, , "Ad creative creation failed"],
+    [c(1815694), ANY, Ads, retry: false, user_action: true, , "The ad account is disabled or restricted"],
+    // Instagram content publishing, codes that also have a Graph API meaning (e.g. 2, 4) are left to the rows above.
+    [c(-1), c(2207001), InstagramPublishing, retry: true, user_action: false, , "Instagram server error"],
+    [c(-2), c(2207003), InstagramPublishing, retry: true, user_action: false, , "Timed out downloading the media"],
+    [c(36000), c(2207004), InstagramPublishing, retry: false, user_action: false, , "The image is too large"],
+    [c(36001), c(2207005), InstagramPublishing, retry: false, user_action: false, , "Unsupported image format"],
+    [c(24), c(2207006), InstagramPublishing, retry: false, user_action: false, , "The media was not found, e.g. the URL is not publicly accessible"],
+    [c(-1), c(2207008), InstagramPublishing, retry: true, user_action: false, , "Temporary error publishing a container"],
+    [c(36003), c(2207009), InstagramPublishing, retry: false, user_action: false, , "The aspect ratio is not supported"],
+    [c(24), c(2207020), InstagramPublishing, retry: false, user_action: false, , "The media has expired, create a new container"],
+    [c(352), c(2207026), InstagramPublishing, retry: false, user_action: false, , "Unsupported video format"],
+    [c(9007), c(2207027), InstagramPublishing, retry: true, user_action: false, , "The media is not ready for publishing yet"],
+    [c(-1), c(2207032), InstagramPublishing, retry: true, user_action: false, , "Creating the media failed"],
+    [c(9), c(2207042), InstagramPublishing, retry: false, user_action: false, , "The account reached its maximum number of posts per day"],
+    [c(25), c(2207050), InstagramPublishing, retry: false, user_action: true, , "The Instagram account is inactive or restricted"],
+    [c(9004), c(2207052), InstagramPublishing, retry: false, user_action: false, , "The media could not be fetched from the URL"],
+    [r(-2, -1), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+    [c(9), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+    [r(24, 25), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+    [c(352), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+    [r(9004, 9007), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+    [r(36000, 36004), r(2207000, 2207999), InstagramPublishing, retry: false, user_action: false, , "Instagram content publishing error"],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let entry = lookup(190, Some(463)).unwrap();
+        assert_eq!(entry.description, "The access token has expired");
+        assert!(entry.user_action);
+
+        let entry = lookup(190, Some(1)).unwrap();
+        assert_eq!(entry.subcode, None);
+        assert_eq!(entry.category, ErrorCategory::AccessToken);
+
+        assert_eq!(
+            lookup(250, None).unwrap().known_error_case,
+            Some(KnownErrorCase::PermissionNotGrantedOrRemoved)
+        );
+        assert_eq!(
+            lookup(80004, None).unwrap().category,
+            ErrorCategory::RateLimit
+        );
+        assert_eq!(
+            lookup(17, Some(2446079)).unwrap().description,
+            "User request limit reached for the ad account"
+        );
+        assert_eq!(
+            lookup(10, Some(2018278)).unwrap().category,
+            ErrorCategory::Messaging
+        );
+        assert_eq!(
+            lookup(10, Some(1)).unwrap().category,
+            ErrorCategory::Permission
+        );
+
+        let entry = lookup(9007, Some(2207027)).unwrap();
+        assert!(entry.retry);
+        // The daily post limit won't lift within a retry backoff.
+        let entry = lookup(9, Some(2207042)).unwrap();
+        assert!(!entry.retry);
+        let entry = lookup(36003, Some(2207999)).unwrap();
+        assert_eq!(entry.category, ErrorCategory::InstagramPublishing);
+        assert!(!entry.retry);
+        // A Graph API code keeps its category with an Instagram subcode.
+        assert_eq!(
+            lookup(2, Some(2207001)).unwrap().category,
+            ErrorCategory::Service
+        );
+        assert_eq!(
+            lookup(4, Some(2207051)).unwrap().category,
+            ErrorCategory::RateLimit
+        );
+
+        assert!(lookup(999_999, None).is_none());
+    }
+}
@@ -5,6 +5,11 @@ use serde::{Deserialize, Serialize};
 use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
 use serde_json::{Map, Value};
 
+//
+pub mod catalogue;
+
+pub use catalogue::{ErrorCatalogueEntry, ErrorCategory, ERROR_CATALOGUE};
+
 //
 const CODE_STATUS_CODE_AND_BODY: i32 = -2_147_483_001;
 
@@ -20,10 +25,7 @@ pub struct Error {
     pub error_user_title: Option<String>,
     pub error_user_msg: Option<String>,
     pub fbtrace_id: Option<String>,
-    /*
-    is_transient https://developers.facebook.com/docs/instagram-api/reference/error-codes
-    error_data
-    */
+    // is_transient and error_data are kept here as is, see the accessors.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     _extra: Option<Map<String, Value>>,
 }
@@ -67,6 +69,46 @@ impl Error {
     }
 }
 
+impl Error {
+    // https://developers.facebook.com/docs/instagram-api/reference/error-codes
+    // false when absent.
+    pub fn is_transient(&self) -> bool {
+        self.extra()
+            .and_then(|x| x.get("is_transient"))
+            .and_then(|x| x.as_bool())
+            .unwrap_or(false)
+    }
+
+    // error_data is either an object or a string containing a JSON object.
+    pub fn error_data(&self) -> Option<ErrorData> {
+        let value = self.extra()?.get("error_data")?;
+        match value {
+            Value::String(s) => serde_json::from_str(s).ok(),
+            value => serde_json::from_value(value.to_owned()).ok(),
+        }
+    }
+
+    // error_user_title and error_user_msg are in the language of the locale parameter of the request.
+    pub fn user_message(&self) -> Option<ErrorUserMessage<'_>> {
+        self.error_user_msg
+            .as_deref()
+            .map(|message| ErrorUserMessage {
+                title: self.error_user_title.as_deref(),
+                message,
+            })
+    }
+}
+
+impl Error {
+    pub fn catalogue_entry(&self) -> Option<&'static ErrorCatalogueEntry> {
+        catalogue::lookup(self.code, self.error_subcode)
+    }
+}
+
+/*
+The message checks are fallbacks for errors without error_subcode,
+the messages are not documented and may change.
+*/
 impl Error {
     pub fn is_error_validating_access_token(&self) -> bool {
         self.message
@@ -75,15 +117,23 @@ impl Error {
     }
 
     pub fn is_access_token_session_has_been_invalidated(&self) -> bool {
-        self.message
-            .to_lowercase()
-            .contains("session has been invalidated")
+        match self.error_subcode {
+            Some(error_subcode) => error_subcode == 460,
+            None => self
+                .message
+                .to_lowercase()
+                .contains("session has been invalidated"),
+        }
     }
 
     pub fn is_access_token_session_has_expired(&self) -> bool {
-        self.message.to_lowercase().contains("session has expired")
+        match self.error_subcode {
+            Some(error_subcode) => error_subcode == 463,
+            None => self.message.to_lowercase().contains("session has expired"),
+        }
     }
 
+    // No documented error_subcode.
     pub fn is_access_token_session_key_is_malformed(&self) -> bool {
         self.message
             .to_lowercase()
@@ -101,9 +151,17 @@ pub enum ErrorType {
     Other(String),
 }
 
+// e.g. `Invalid OAuth access token. (code: 190, subcode: 463, fbtrace_id: AOl1gSqZRMyPzEMw9OJXzYh)`
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        write!(f, "{} (code: {}", self.message, self.code)?;
+        if let Some(error_subcode) = self.error_subcode {
+            write!(f, ", subcode: {error_subcode}")?;
+        }
+        if let Some(fbtrace_id) = &self.fbtrace_id {
+            write!(f, ", fbtrace_id: {fbtrace_id}")?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -112,7 +170,52 @@ impl std::error::Error for Error {}
 //
 //
 //
-#[derive(Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ErrorData {
+    // The fields the error is about, e.g. [["daily_budget"]] from the Marketing API.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_blame_field_specs",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub blame_field_specs: Vec<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+// Items are either a field path (["targeting", "geo_locations"]) or a single field name.
+fn deserialize_blame_field_specs<'de, D>(deserializer: D) -> Result<Vec<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Spec {
+        Path(Vec<String>),
+        Field(String),
+    }
+
+    Ok(Option::<Vec<Spec>>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| match x {
+            Spec::Path(x) => x,
+            Spec::Field(x) => vec![x],
+        })
+        .collect())
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorUserMessage<'a> {
+    pub title: Option<&'a str>,
+    pub message: &'a str,
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum KnownErrorCase {
     ApiTooManyCalls,
@@ -120,6 +223,9 @@ pub enum KnownErrorCase {
     AccessTokenExpiredOrRevokedOrInvalid,
     PermissionNotGrantedOrRemoved,
     RetryLater,
+    PageTooManyCalls,
+    CustomRateLimitReached,
+    BusinessUseCaseTooManyCalls,
 }
 
 impl KnownErrorCase {
@@ -142,6 +248,18 @@ impl KnownErrorCase {
     pub fn is_retry_later(&self) -> bool {
         matches!(self, Self::RetryLater)
     }
+
+    pub fn is_page_too_many_calls(&self) -> bool {
+        matches!(self, Self::PageTooManyCalls)
+    }
+
+    pub fn is_custom_rate_limit_reached(&self) -> bool {
+        matches!(self, Self::CustomRateLimitReached)
+    }
+
+    pub fn is_business_use_case_too_many_calls(&self) -> bool {
+        matches!(self, Self::BusinessUseCaseTooManyCalls)
+    }
 }
 
 impl core::fmt::Display for KnownErrorCase {
@@ -154,27 +272,15 @@ impl std::error::Error for KnownErrorCase {}
 
 impl Error {
     pub fn to_known_error_case(&self) -> Option<KnownErrorCase> {
-        if let Some(error_subcode) = self.error_subcode {
-            #[allow(clippy::single_match)]
-            match error_subcode {
-                463 | 467 => return Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid),
-                _ => {}
-            }
+        if let Some(known_error_case) = self
+            .catalogue_entry()
+            .and_then(|entry| entry.known_error_case)
+        {
+            return Some(known_error_case);
         }
 
-        match self.code {
-            102 => {
-                if self.error_subcode.is_none() {
-                    return Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid);
-                }
-            }
-            2 => return Some(KnownErrorCase::RetryLater),
-            4 => return Some(KnownErrorCase::ApiTooManyCalls),
-            17 => return Some(KnownErrorCase::ApiUserTooManyCalls),
-            10 => return Some(KnownErrorCase::PermissionNotGrantedOrRemoved),
-            190 => return Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid),
-            200..=299 => return Some(KnownErrorCase::PermissionNotGrantedOrRemoved),
-            _ => {}
+        if self.is_error_validating_access_token() {
+            return Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid);
         }
 
         None
@@ -291,4 +397,101 @@ mod tests {
             Err(err) => panic!("{}", err),
         }
     }
+
+    #[test]
+    fn test_to_known_error_case() {
+        let error = |code: i32, error_subcode: Option<i32>, message: &str| Error {
+            message: message.into(),
+            r#type: None,
+            code,
+            error_subcode,
+            error_user_title: None,
+            error_user_msg: None,
+            fbtrace_id: None,
+            _extra: None,
+        };
+
+        let err = error(190, Some(463), "x");
+        assert_eq!(
+            err.to_known_error_case(),
+            Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid)
+        );
+        assert!(err.is_access_token_session_has_expired());
+        assert!(!err.is_access_token_session_has_been_invalidated());
+        assert!(!error(190, Some(467), "Session has expired").is_access_token_session_has_expired());
+
+        assert_eq!(
+            error(32, None, "x").to_known_error_case(),
+            Some(KnownErrorCase::PageTooManyCalls)
+        );
+        assert_eq!(
+            error(80001, None, "x").to_known_error_case(),
+            Some(KnownErrorCase::BusinessUseCaseTooManyCalls)
+        );
+        assert_eq!(error(100, None, "x").to_known_error_case(), None);
+        // The entry has no known error case, the message still says what it is.
+        assert_eq!(
+            error(100, None, "Error validating access token: x").to_known_error_case(),
+            Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid)
+        );
+        assert_eq!(
+            error(999_999, None, "Error validating access token: x").to_known_error_case(),
+            Some(KnownErrorCase::AccessTokenExpiredOrRevokedOrInvalid)
+        );
+        assert_eq!(error(999_999, None, "x").to_known_error_case(), None);
+        assert!(error(999_999, None, "x").catalogue_entry().is_none());
+        assert_eq!(
+            Error::new_with_status_code_and_body(502, "x").to_known_error_case(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_accessors_and_display() {
+        let content = r#"{"error":{"message":"Invalid parameter","type":"OAuthException","code":100,"error_subcode":1487390,"is_transient":false,"error_user_title":"Budget Too Low","error_user_msg":"Increase the daily budget.","error_data":{"blame_field_specs":[["daily_budget"],"bid_amount"]},"fbtrace_id":"AbCd"}}"#;
+        let err_json = serde_json::from_str::<ResponseBodyErrJson>(content).unwrap();
+        let error = err_json.error;
+
+        assert!(!error.is_transient());
+        assert_eq!(
+            error.error_data().unwrap().blame_field_specs,
+            vec![
+                vec!["daily_budget".to_owned()],
+                vec!["bid_amount".to_owned()]
+            ]
+        );
+        assert_eq!(
+            error.user_message(),
+            Some(ErrorUserMessage {
+                title: Some("Budget Too Low"),
+                message: "Increase the daily budget."
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid parameter (code: 100, subcode: 1487390, fbtrace_id: AbCd)"
+        );
+
+        // round-trip
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::from_str::<Value>(content).unwrap()["error"]
+        );
+
+        //
+        let content = r#"{"error":{"message":"x","code":2,"is_transient":true,"error_data":"{\"blame_field_specs\":[[\"targeting\",\"geo_locations\"]]}"}}"#;
+        let error = serde_json::from_str::<ResponseBodyErrJson>(content)
+            .unwrap()
+            .error;
+        assert!(error.is_transient());
+        assert_eq!(
+            error.error_data().unwrap().blame_field_specs,
+            vec![vec!["targeting".to_owned(), "geo_locations".to_owned()]]
+        );
+        assert!(error.user_message().is_none());
+        assert_eq!(error.to_string(), "x (code: 2)");
+        let error: Error = serde_json::from_value(serde_json::to_value(&error).unwrap()).unwrap();
+        assert!(error.is_transient());
+        assert_eq!(error.to_string(), "x (code: 2)");
+    }
 }
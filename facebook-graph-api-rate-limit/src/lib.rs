@@ -0,0 +1,13 @@
+//! https://developers.facebook.com/docs/graph-api/overview/rate-limiting
+
+pub mod throttle;
+pub mod usage;
+
+pub use throttle::{
+    request_object_id, Clock, FakeClock, SystemClock, ThrottleConfig, ThrottleKey,
+    ThrottleObjectId, ThrottleState, Throttler, ThrottlingClient,
+};
+pub use usage::{
+    AdAccountUsage, AppUsage, BusinessUseCaseType, BusinessUseCaseUsage, BusinessUseCaseUsageItem,
+    PageUsage, Usage,
+};
@@ -0,0 +1,536 @@
+//! Slows requests down before Facebook starts returning code 4, 17, 32 or 613.
+//!
+//! Usage is tracked per app, per Page (X-Page-Usage) and per business use case object (X-Business-Use-Case-Usage).
+//! The Page or business object of a request is its ThrottleObjectId extension, e.g. for `/me/...` calls with a Page access token,
+//! otherwise the numeric id at the start of its path, e.g. `/v15.0/{page-id}/feed`.
+//! One Throttler is for one app.
+
+use core::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use http_api_client::{async_trait, Client, RetryableClient};
+use http_api_client_endpoint::{Body, Request, Response};
+
+use crate::Usage;
+
+//
+//
+//
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// Only moves when advance is called.
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, dur: Duration) {
+        *self.now.lock().expect("") += dur;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().expect("")
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThrottleKey {
+    App,
+    Page(u64),
+    BusinessUseCase(u64),
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleState {
+    // 0-100, the highest of call_count, total_time and total_cputime.
+    pub pct: u32,
+    pub paused_until: Option<Instant>,
+    pub updated_at: Instant,
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleConfig {
+    // Below it requests are not delayed.
+    pub threshold_pct: u32,
+    // The delay grows linearly from zero at threshold_pct to max_delay at 100.
+    pub max_delay: Duration,
+    // Used at 100 when Facebook does not say when access is regained.
+    pub exhausted_pause: Duration,
+    // Usage is a rolling one hour window, a state not updated for this long is ignored.
+    pub stale_after: Duration,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            threshold_pct: 75,
+            max_delay: Duration::from_secs(10),
+            exhausted_pause: Duration::from_secs(60),
+            stale_after: Duration::from_secs(60 * 5),
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub struct Throttler<K = SystemClock> {
+    config: ThrottleConfig,
+    clock: K,
+    states: Mutex<HashMap<ThrottleKey, ThrottleState>>,
+}
+
+impl Throttler<SystemClock> {
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self::with_clock(config, SystemClock)
+    }
+}
+
+impl<K> Throttler<K>
+where
+    K: Clock,
+{
+    pub fn with_clock(config: ThrottleConfig, clock: K) -> Self {
+        Self {
+            config,
+            clock,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &ThrottleConfig {
+        &self.config
+    }
+
+    pub fn clock(&self) -> &K {
+        &self.clock
+    }
+
+    pub fn state(&self, key: &ThrottleKey) -> Option<ThrottleState> {
+        self.states.lock().expect("").get(key).copied()
+    }
+
+    // object_id is the Page or business object of the request.
+    pub fn observe(&self, object_id: Option<u64>, usage: &Usage) {
+        let now = self.clock.now();
+        let mut states = self.states.lock().expect("");
+
+        let mut update = |key: ThrottleKey, pct: u32, regain_access_in: Option<Duration>| {
+            let paused_until = match regain_access_in {
+                Some(dur) => Some(now + dur),
+                None if pct >= 100 => Some(now + self.config.exhausted_pause),
+                None => None,
+            };
+            states.insert(
+                key,
+                ThrottleState {
+                    pct,
+                    paused_until,
+                    updated_at: now,
+                },
+            );
+        };
+
+        if let Some(app) = &usage.app {
+            update(ThrottleKey::App, app.max_pct(), None);
+        }
+        if let (Some(page), Some(object_id)) = (&usage.page, object_id) {
+            update(
+                ThrottleKey::Page(object_id),
+                page.max_pct(),
+                page.regain_access_in(),
+            );
+        }
+        if let Some(business_use_case) = &usage.business_use_case {
+            for (id, items) in business_use_case.0.iter() {
+                if let Ok(id) = id.parse::<u64>() {
+                    update(
+                        ThrottleKey::BusinessUseCase(id),
+                        items.iter().map(|x| x.max_pct()).max().unwrap_or(0),
+                        items.iter().filter_map(|x| x.regain_access_in()).max(),
+                    );
+                }
+            }
+        }
+    }
+
+    // How long to wait before sending a request for object_id.
+    pub fn delay(&self, object_id: Option<u64>) -> Duration {
+        let mut keys = vec![ThrottleKey::App];
+        if let Some(object_id) = object_id {
+            keys.push(ThrottleKey::Page(object_id));
+            keys.push(ThrottleKey::BusinessUseCase(object_id));
+        }
+
+        let now = self.clock.now();
+        let states = self.states.lock().expect("");
+        keys.iter()
+            .filter_map(|key| states.get(key))
+            .map(|state| self.state_delay(state, now))
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn state_delay(&self, state: &ThrottleState, now: Instant) -> Duration {
+        if let Some(paused_until) = state.paused_until {
+            if paused_until > now {
+                return paused_until - now;
+            }
+            return Duration::ZERO;
+        }
+
+        if now.duration_since(state.updated_at) >= self.config.stale_after
+            || state.pct < self.config.threshold_pct
+        {
+            return Duration::ZERO;
+        }
+
+        let range = 100_u32.saturating_sub(self.config.threshold_pct).max(1);
+        let over = (state.pct - self.config.threshold_pct).min(range);
+        self.config.max_delay * over / range
+    }
+}
+
+//
+// The Page or business object of a request whose path doesn't start with it, set as a request extension,
+// e.g. with Client::respond_endpoint_with_callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThrottleObjectId(pub u64);
+
+// The ThrottleObjectId extension,
+// otherwise the first numeric path segment after the optional version, e.g. `/v15.0/{page-id}/feed`.
+pub fn request_object_id<B>(request: &Request<B>) -> Option<u64> {
+    if let Some(ThrottleObjectId(object_id)) = request.extensions().get::<ThrottleObjectId>() {
+        return Some(*object_id);
+    }
+
+    let mut segments = request
+        .uri()
+        .path()
+        .split('/')
+        .filter(|x| !x.is_empty())
+        .peekable();
+    if segments.peek().map(|x| x.starts_with('v')) == Some(true) {
+        segments.next();
+    }
+    segments.next().and_then(|x| x.parse::<u64>().ok())
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub struct ThrottlingClient<C, K = SystemClock> {
+    client: C,
+    throttler: Throttler<K>,
+}
+
+impl<C, K> ThrottlingClient<C, K> {
+    pub fn new(client: C, throttler: Throttler<K>) -> Self {
+        Self { client, throttler }
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    pub fn throttler(&self) -> &Throttler<K> {
+        &self.throttler
+    }
+}
+
+impl<C, K> ThrottlingClient<C, K>
+where
+    C: RetryableClient + Send + Sync,
+    K: Clock + Send + Sync,
+{
+    // Throttles the request as one for object_id, e.g. `/me/feed` with a Page access token.
+    pub async fn respond_for(
+        &self,
+        object_id: u64,
+        mut request: Request<Body>,
+    ) -> Result<Response<Body>, C::RespondError> {
+        request.extensions_mut().insert(ThrottleObjectId(object_id));
+        self.respond(request).await
+    }
+}
+
+#[async_trait]
+impl<C, K> Client for ThrottlingClient<C, K>
+where
+    C: RetryableClient + Send + Sync,
+    K: Clock + Send + Sync,
+{
+    type RespondError = C::RespondError;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        let object_id = request_object_id(&request);
+
+        let delay = self.throttler.delay(object_id);
+        if !delay.is_zero() {
+            self.client.sleep(delay).await;
+        }
+
+        let response = self.client.respond(request).await?;
+        self.throttler
+            .observe(object_id, &Usage::from_headers(response.headers()));
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<C, K> RetryableClient for ThrottlingClient<C, K>
+where
+    C: RetryableClient + Send + Sync,
+    K: Clock + Send + Sync,
+{
+    async fn sleep(&self, dur: Duration) {
+        self.client.sleep(dur).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Error as IoError;
+
+    use futures_lite::future::block_on;
+
+    use crate::{AppUsage, PageUsage};
+
+    #[test]
+    fn test_throttler() {
+        let clock = FakeClock::new();
+        let throttler = Throttler::with_clock(ThrottleConfig::default(), clock.clone());
+        assert_eq!(throttler.delay(None), Duration::ZERO);
+
+        let usage = |call_count: u32| Usage {
+            app: Some(AppUsage {
+                call_count,
+                total_time: 0,
+                total_cputime: 0,
+            }),
+            ..Default::default()
+        };
+
+        throttler.observe(None, &usage(50));
+        assert_eq!(throttler.delay(None), Duration::ZERO);
+        throttler.observe(None, &usage(80));
+        assert_eq!(throttler.delay(None), Duration::from_secs(2));
+        throttler.observe(None, &usage(95));
+        assert_eq!(throttler.delay(Some(123)), Duration::from_secs(8));
+
+        clock.advance(Duration::from_secs(60 * 5));
+        assert_eq!(throttler.delay(None), Duration::ZERO);
+
+        //
+        throttler.observe(None, &usage(100));
+        assert_eq!(throttler.delay(None), Duration::from_secs(60));
+        clock.advance(Duration::from_secs(45));
+        assert_eq!(throttler.delay(None), Duration::from_secs(15));
+        clock.advance(Duration::from_secs(15));
+        assert_eq!(throttler.delay(None), Duration::ZERO);
+
+        //
+        throttler.observe(
+            Some(123),
+            &Usage {
+                page: Some(PageUsage {
+                    call_count: 100,
+                    total_time: 0,
+                    total_cputime: 0,
+                    estimated_time_to_regain_access: Some(3),
+                }),
+                ..Default::default()
+            },
+        );
+        assert_eq!(throttler.state(&ThrottleKey::Page(123)).unwrap().pct, 100);
+        assert_eq!(throttler.delay(Some(123)), Duration::from_secs(180));
+        assert_eq!(throttler.delay(Some(456)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_request_object_id() {
+        for (uri, object_id) in [
+            ("https://graph.facebook.com/v15.0/123/feed", Some(123)),
+            ("https://graph.facebook.com/123", Some(123)),
+            ("https://graph.facebook.com/v15.0/me/accounts", None),
+            ("https://graph.facebook.com/v15.0/debug_token", None),
+        ] {
+            let request = Request::builder().uri(uri).body(()).unwrap();
+            assert_eq!(request_object_id(&request), object_id, "{uri}");
+        }
+
+        let request = Request::builder()
+            .uri("https://graph.facebook.com/v15.0/me/feed")
+            .extension(ThrottleObjectId(123))
+            .body(())
+            .unwrap();
+        assert_eq!(request_object_id(&request), Some(123));
+    }
+
+    struct MockClient {
+        clock: FakeClock,
+        responses: Mutex<Vec<&'static str>>,
+        sleeps: Mutex<Vec<Duration>>,
+    }
+
+    #[async_trait]
+    impl Client for MockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            _request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let business_use_case_usage = self.responses.lock().unwrap().remove(0);
+            Ok(Response::builder()
+                .status(200)
+                .header("X-Business-Use-Case-Usage", business_use_case_usage)
+                .body(b"{}".to_vec())
+                .unwrap())
+        }
+    }
+
+    #[async_trait]
+    impl RetryableClient for MockClient {
+        async fn sleep(&self, dur: Duration) {
+            self.sleeps.lock().unwrap().push(dur);
+            self.clock.advance(dur);
+        }
+    }
+
+    #[test]
+    fn test_throttling_client() {
+        block_on(async {
+            let clock = FakeClock::new();
+            let client = ThrottlingClient::new(
+                MockClient {
+                    clock: clock.clone(),
+                    responses: Mutex::new(vec![
+                        r#"{"123":[{"type":"pages","call_count":100,"total_cputime":1,"total_time":1,"estimated_time_to_regain_access":2}]}"#,
+                        r#"{"123":[{"type":"pages","call_count":5,"total_cputime":1,"total_time":1,"estimated_time_to_regain_access":0}]}"#,
+                        r#"{}"#,
+                    ]),
+                    sleeps: Mutex::new(vec![]),
+                },
+                Throttler::with_clock(ThrottleConfig::default(), clock),
+            );
+
+            for uri in [
+                "https://graph.facebook.com/v15.0/123/feed",
+                "https://graph.facebook.com/v15.0/123/feed",
+                "https://graph.facebook.com/v15.0/456/feed",
+            ] {
+                let request = Request::builder().uri(uri).body(vec![]).unwrap();
+                client.respond(request).await.unwrap();
+            }
+
+            assert_eq!(
+                *client.client().sleeps.lock().unwrap(),
+                vec![Duration::from_secs(120)]
+            );
+            assert_eq!(
+                client
+                    .throttler()
+                    .state(&ThrottleKey::BusinessUseCase(123))
+                    .unwrap()
+                    .pct,
+                5
+            );
+        })
+    }
+
+    struct PageUsageMockClient;
+
+    #[async_trait]
+    impl Client for PageUsageMockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            _request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            Ok(Response::builder()
+                .status(200)
+                .header(
+                    "X-Page-Usage",
+                    r#"{"call_count":90,"total_cputime":1,"total_time":1}"#,
+                )
+                .body(b"{}".to_vec())
+                .unwrap())
+        }
+    }
+
+    #[async_trait]
+    impl RetryableClient for PageUsageMockClient {
+        async fn sleep(&self, _dur: Duration) {}
+    }
+
+    #[test]
+    fn test_throttling_client_respond_for() {
+        block_on(async {
+            let client = ThrottlingClient::new(
+                PageUsageMockClient,
+                Throttler::new(ThrottleConfig::default()),
+            );
+
+            let request = || {
+                Request::builder()
+                    .uri("https://graph.facebook.com/v15.0/me/feed")
+                    .body(vec![])
+                    .unwrap()
+            };
+            client.respond(request()).await.unwrap();
+            assert!(client.throttler().state(&ThrottleKey::Page(123)).is_none());
+
+            client.respond_for(123, request()).await.unwrap();
+            assert_eq!(
+                client
+                    .throttler()
+                    .state(&ThrottleKey::Page(123))
+                    .unwrap()
+                    .pct,
+                90
+            );
+        })
+    }
+}
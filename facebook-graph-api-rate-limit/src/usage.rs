@@ -0,0 +1,277 @@
+//! Usage headers returned with Graph API responses, percentages are 0-100.
+
+use core::time::Duration;
+use std::collections::HashMap;
+
+use http_api_client_endpoint::http::HeaderMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+
+//
+pub const HEADER_APP_USAGE: &str = "x-app-usage";
+pub const HEADER_PAGE_USAGE: &str = "x-page-usage";
+pub const HEADER_BUSINESS_USE_CASE_USAGE: &str = "x-business-use-case-usage";
+pub const HEADER_AD_ACCOUNT_USAGE: &str = "x-ad-account-usage";
+
+//
+//
+//
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Usage {
+    pub app: Option<AppUsage>,
+    pub page: Option<PageUsage>,
+    pub business_use_case: Option<BusinessUseCaseUsage>,
+    pub ad_account: Option<AdAccountUsage>,
+}
+
+impl Usage {
+    // Missing or invalid headers are None.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            app: header_json(headers, HEADER_APP_USAGE),
+            page: header_json(headers, HEADER_PAGE_USAGE),
+            business_use_case: header_json(headers, HEADER_BUSINESS_USE_CASE_USAGE),
+            ad_account: header_json(headers, HEADER_AD_ACCOUNT_USAGE),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.app.is_none()
+            && self.page.is_none()
+            && self.business_use_case.is_none()
+            && self.ad_account.is_none()
+    }
+
+    // The highest percentage of all usages.
+    pub fn max_pct(&self) -> Option<u32> {
+        [
+            self.app.as_ref().map(|x| x.max_pct()),
+            self.page.as_ref().map(|x| x.max_pct()),
+            self.business_use_case.as_ref().and_then(|x| x.max_pct()),
+            self.ad_account
+                .as_ref()
+                .map(|x| x.acc_id_util_pct.ceil() as u32),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+
+    // The longest wait reported, None when no usage is throttled.
+    pub fn regain_access_in(&self) -> Option<Duration> {
+        [
+            self.page.as_ref().and_then(|x| x.regain_access_in()),
+            self.business_use_case
+                .as_ref()
+                .and_then(|x| x.regain_access_in()),
+            self.ad_account.as_ref().and_then(|x| x.reset_in()),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+}
+
+fn header_json<T: DeserializeOwned>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers
+        .get(name)
+        .and_then(|x| serde_json::from_slice(x.as_bytes()).ok())
+}
+
+//
+// X-App-Usage
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppUsage {
+    #[serde(default)]
+    pub call_count: u32,
+    #[serde(default)]
+    pub total_time: u32,
+    #[serde(default)]
+    pub total_cputime: u32,
+}
+
+impl AppUsage {
+    pub fn max_pct(&self) -> u32 {
+        self.call_count.max(self.total_time).max(self.total_cputime)
+    }
+}
+
+//
+// X-Page-Usage
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageUsage {
+    #[serde(default)]
+    pub call_count: u32,
+    #[serde(default)]
+    pub total_time: u32,
+    #[serde(default)]
+    pub total_cputime: u32,
+    // Minutes.
+    pub estimated_time_to_regain_access: Option<u32>,
+}
+
+impl PageUsage {
+    pub fn max_pct(&self) -> u32 {
+        self.call_count.max(self.total_time).max(self.total_cputime)
+    }
+
+    pub fn regain_access_in(&self) -> Option<Duration> {
+        self.estimated_time_to_regain_access
+            .filter(|x| *x > 0)
+            .map(|x| Duration::from_secs(x as u64 * 60))
+    }
+}
+
+//
+// X-Business-Use-Case-Usage, keyed by business object id, e.g. a Page id.
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BusinessUseCaseUsage(pub HashMap<String, Vec<BusinessUseCaseUsageItem>>);
+
+impl BusinessUseCaseUsage {
+    pub fn items(&self) -> impl Iterator<Item = &BusinessUseCaseUsageItem> {
+        self.0.values().flatten()
+    }
+
+    pub fn max_pct(&self) -> Option<u32> {
+        self.items().map(|x| x.max_pct()).max()
+    }
+
+    pub fn regain_access_in(&self) -> Option<Duration> {
+        self.items().filter_map(|x| x.regain_access_in()).max()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BusinessUseCaseUsageItem {
+    pub r#type: BusinessUseCaseType,
+    #[serde(default)]
+    pub call_count: u32,
+    #[serde(default)]
+    pub total_cputime: u32,
+    #[serde(default)]
+    pub total_time: u32,
+    // Minutes.
+    #[serde(default)]
+    pub estimated_time_to_regain_access: u32,
+}
+
+impl BusinessUseCaseUsageItem {
+    pub fn max_pct(&self) -> u32 {
+        self.call_count.max(self.total_time).max(self.total_cputime)
+    }
+
+    pub fn regain_access_in(&self) -> Option<Duration> {
+        Some(self.estimated_time_to_regain_access)
+            .filter(|x| *x > 0)
+            .map(|x| Duration::from_secs(x as u64 * 60))
+    }
+}
+
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BusinessUseCaseType {
+    AdsInsights,
+    AdsManagement,
+    CustomAudience,
+    Instagram,
+    Leadgen,
+    Messenger,
+    Pages,
+    #[serde(other)]
+    Other(String),
+}
+
+//
+// X-Ad-Account-Usage
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct AdAccountUsage {
+    #[serde(default)]
+    pub acc_id_util_pct: f64,
+    // Seconds.
+    #[serde(default)]
+    pub reset_time_duration: u64,
+    pub ads_api_access_tier: Option<String>,
+}
+
+impl AdAccountUsage {
+    pub fn reset_in(&self) -> Option<Duration> {
+        Some(self.reset_time_duration)
+            .filter(|x| *x > 0)
+            .map(Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http_api_client_endpoint::http::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(Usage::from_headers(&headers).is_empty());
+
+        headers.insert(
+            "X-App-Usage",
+            HeaderValue::from_static(r#"{"call_count":28,"total_time":25,"total_cputime":25}"#),
+        );
+        headers.insert(
+            "X-Page-Usage",
+            HeaderValue::from_static(
+                r#"{"call_count":5,"total_cputime":1,"total_time":2,"estimated_time_to_regain_access":0}"#,
+            ),
+        );
+        headers.insert(
+            "X-Business-Use-Case-Usage",
+            HeaderValue::from_static(
+                r#"{"103455271248220":[{"type":"pages","call_count":100,"total_cputime":25,"total_time":25,"estimated_time_to_regain_access":19},{"type":"whatsapp","call_count":1,"total_cputime":1,"total_time":1,"estimated_time_to_regain_access":0}]}"#,
+            ),
+        );
+        headers.insert(
+            "X-Ad-Account-Usage",
+            HeaderValue::from_static(
+                r#"{"acc_id_util_pct":9.67,"reset_time_duration":0,"ads_api_access_tier":"standard_access"}"#,
+            ),
+        );
+
+        let usage = Usage::from_headers(&headers);
+        assert_eq!(
+            usage.app,
+            Some(AppUsage {
+                call_count: 28,
+                total_time: 25,
+                total_cputime: 25
+            })
+        );
+        assert_eq!(usage.page.as_ref().unwrap().max_pct(), 5);
+        assert!(usage.page.as_ref().unwrap().regain_access_in().is_none());
+        let items = usage
+            .business_use_case
+            .as_ref()
+            .unwrap()
+            .0
+            .get("103455271248220")
+            .unwrap();
+        assert_eq!(items[0].r#type, BusinessUseCaseType::Pages);
+        assert_eq!(
+            items[1].r#type,
+            BusinessUseCaseType::Other("whatsapp".into())
+        );
+        assert_eq!(
+            usage.ad_account.as_ref().unwrap().ads_api_access_tier,
+            Some("standard_access".into())
+        );
+
+        assert_eq!(usage.max_pct(), Some(100));
+        assert_eq!(usage.regain_access_in(), Some(Duration::from_secs(19 * 60)));
+
+        //
+        headers.insert("X-App-Usage", HeaderValue::from_static("x"));
+        assert!(Usage::from_headers(&headers).app.is_none());
+    }
+}
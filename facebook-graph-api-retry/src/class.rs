@@ -0,0 +1,222 @@
+use facebook_graph_api_object_error::{Error, ErrorCategory, KnownErrorCase};
+use http_api_client_endpoint::{http::StatusCode, Body, Response};
+use serde::Deserialize;
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    // Error catalogue category RateLimit, or HTTP 429.
+    RateLimited,
+    // is_transient, a retryable error catalogue entry, or HTTP 5xx.
+    Transient,
+    // The client failed to get a response.
+    Network,
+    // Expired tokens, missing permissions, invalid parameters and so on, never retried.
+    Permanent,
+}
+
+impl ErrorClass {
+    // error is None when the response body is not a Graph API error.
+    pub fn from_response(status_code: StatusCode, error: Option<&Error>) -> Self {
+        if let Some(error) = error {
+            if let Some(class) = Self::from_error(error) {
+                return class;
+            }
+        }
+
+        Self::from_status_code(status_code)
+    }
+
+    pub fn from_error(error: &Error) -> Option<Self> {
+        if error.is_transient() {
+            return Some(Self::Transient);
+        }
+
+        if let Some(entry) = error.catalogue_entry() {
+            return Some(if entry.category == ErrorCategory::RateLimit {
+                Self::RateLimited
+            } else if entry.retry {
+                Self::Transient
+            } else {
+                Self::Permanent
+            });
+        }
+
+        // Not in the catalogue, e.g. an undocumented code with an "Error validating access token" message.
+        error.to_known_error_case().map(|x| match x {
+            KnownErrorCase::ApiTooManyCalls
+            | KnownErrorCase::ApiUserTooManyCalls
+            | KnownErrorCase::PageTooManyCalls
+            | KnownErrorCase::CustomRateLimitReached
+            | KnownErrorCase::BusinessUseCaseTooManyCalls => Self::RateLimited,
+            KnownErrorCase::RetryLater => Self::Transient,
+            _ => Self::Permanent,
+        })
+    }
+
+    // None when the response is a success.
+    pub fn from_http_response(response: &Response<Body>) -> Option<Self> {
+        if response.status().is_success() {
+            return None;
+        }
+
+        let error = serde_json::from_slice::<ErrJson>(response.body())
+            .ok()
+            .map(|x| x.error);
+        Some(Self::from_response(response.status(), error.as_ref()))
+    }
+
+    pub fn from_status_code(status_code: StatusCode) -> Self {
+        if status_code == StatusCode::TOO_MANY_REQUESTS {
+            Self::RateLimited
+        } else if status_code.is_server_error() {
+            Self::Transient
+        } else {
+            Self::Permanent
+        }
+    }
+}
+
+impl core::fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrJson {
+    error: Error,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(code: i32, error_subcode: Option<i32>, is_transient: Option<bool>) -> Error {
+        let mut value = serde_json::json!({
+            "message": "x",
+            "type": "OAuthException",
+            "code": code,
+            "fbtrace_id": "x",
+        });
+        if let Some(error_subcode) = error_subcode {
+            value["error_subcode"] = error_subcode.into();
+        }
+        if let Some(is_transient) = is_transient {
+            value["is_transient"] = is_transient.into();
+        }
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_from_response() {
+        for (status_code, error, class) in [
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(4, None, None)),
+                ErrorClass::RateLimited,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(17, None, None)),
+                ErrorClass::RateLimited,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(32, None, None)),
+                ErrorClass::RateLimited,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(80004, None, None)),
+                ErrorClass::RateLimited,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(error(2, None, None)),
+                ErrorClass::Transient,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(1, None, None)),
+                ErrorClass::Transient,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(100, None, Some(true))),
+                ErrorClass::Transient,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(100, None, Some(false))),
+                ErrorClass::Permanent,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(190, Some(463), None)),
+                ErrorClass::Permanent,
+            ),
+            (
+                StatusCode::FORBIDDEN,
+                Some(error(200, None, None)),
+                ErrorClass::Permanent,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(error(100, None, None)),
+                ErrorClass::Permanent,
+            ),
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Some(error(999_999, None, None)),
+                ErrorClass::Transient,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(9007, Some(2207027), None)),
+                ErrorClass::Transient,
+            ),
+            (
+                StatusCode::BAD_REQUEST,
+                Some(error(17, Some(2446079), None)),
+                ErrorClass::RateLimited,
+            ),
+            (StatusCode::BAD_GATEWAY, None, ErrorClass::Transient),
+            (StatusCode::TOO_MANY_REQUESTS, None, ErrorClass::RateLimited),
+            (StatusCode::NOT_FOUND, None, ErrorClass::Permanent),
+        ] {
+            assert_eq!(
+                ErrorClass::from_response(status_code, error.as_ref()),
+                class,
+                "{status_code} {error:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_http_response() {
+        let response = Response::builder()
+            .status(400)
+            .body(
+                br#"{"error":{"message":"x","type":"OAuthException","code":4,"fbtrace_id":"x"}}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        assert_eq!(
+            ErrorClass::from_http_response(&response),
+            Some(ErrorClass::RateLimited)
+        );
+
+        let response = Response::builder()
+            .status(503)
+            .body(b"Service Unavailable".to_vec())
+            .unwrap();
+        assert_eq!(
+            ErrorClass::from_http_response(&response),
+            Some(ErrorClass::Transient)
+        );
+
+        let response = Response::builder().status(200).body(vec![]).unwrap();
+        assert_eq!(ErrorClass::from_http_response(&response), None);
+    }
+}
@@ -0,0 +1,313 @@
+use core::time::Duration;
+use std::collections::HashMap;
+
+use http_api_client::{async_trait, Client, RetryableClient};
+use http_api_client_endpoint::{Body, Request, Response};
+
+use crate::{ErrorClass, RetryDecision, RetryPolicy};
+
+//
+// Wraps a client, every request (so every Endpoint sent through it) is retried according to the policy.
+// Attempts are counted per ErrorClass, each class has its own max_attempts and backoff.
+// After the last attempt the last response or respond error is returned as is.
+//
+#[derive(Debug, Clone)]
+pub struct RetryingClient<C> {
+    client: C,
+    policy: RetryPolicy,
+}
+
+impl<C> RetryingClient<C> {
+    pub fn new(client: C, policy: RetryPolicy) -> Self {
+        Self { client, policy }
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+#[async_trait]
+impl<C> Client for RetryingClient<C>
+where
+    C: RetryableClient + Send + Sync,
+{
+    type RespondError = C::RespondError;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        let (mut parts, body) = request.into_parts();
+        // Extensions is not Clone in http 0.2, so only the first attempt carries them.
+        let mut extensions = Some(core::mem::take(&mut parts.extensions));
+
+        let mut attempts = HashMap::<ErrorClass, usize>::new();
+        loop {
+            let (mut attempt_parts, _) = Request::new(()).into_parts();
+            attempt_parts.method = parts.method.to_owned();
+            attempt_parts.uri = parts.uri.to_owned();
+            attempt_parts.version = parts.version;
+            attempt_parts.headers = parts.headers.to_owned();
+            attempt_parts.extensions = extensions.take().unwrap_or_default();
+            let request = Request::from_parts(attempt_parts, body.to_owned());
+
+            let (class, ret) = match self.client.respond(request).await {
+                Ok(response) => match ErrorClass::from_http_response(&response) {
+                    Some(class) => (class, Ok(response)),
+                    None => return Ok(response),
+                },
+                Err(err) => (ErrorClass::Network, Err(err)),
+            };
+
+            if !self.policy.is_retryable_method(class, &parts.method) {
+                return ret;
+            }
+
+            let attempt = attempts.entry(class).or_default();
+            *attempt += 1;
+
+            match self.policy.decide(class, *attempt) {
+                RetryDecision::Retry { after, .. } => self.client.sleep(after).await,
+                RetryDecision::GiveUp { .. } => return ret,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C> RetryableClient for RetryingClient<C>
+where
+    C: RetryableClient + Send + Sync,
+{
+    async fn sleep(&self, dur: Duration) {
+        self.client.sleep(dur).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        io::{Error as IoError, ErrorKind as IoErrorKind},
+        sync::Mutex,
+    };
+
+    use futures_lite::future::block_on;
+
+    use crate::RetryClassPolicy;
+
+    struct MockClient {
+        responses: Mutex<Vec<Result<(u16, String), IoErrorKind>>>,
+        sleeps: Mutex<Vec<Duration>>,
+        extensions: Mutex<Vec<Option<&'static str>>>,
+    }
+
+    impl MockClient {
+        fn new(responses: Vec<Result<(u16, String), IoErrorKind>>) -> Self {
+            Self {
+                responses: Mutex::new(responses),
+                sleeps: Mutex::new(vec![]),
+                extensions: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Client for MockClient {
+        type RespondError = IoError;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            assert_eq!(request.uri().path(), "/v15.0/me");
+            assert_eq!(request.body(), b"BODY");
+            self.extensions
+                .lock()
+                .unwrap()
+                .push(request.extensions().get::<&'static str>().copied());
+            match self.responses.lock().unwrap().remove(0) {
+                Ok((status, body)) => Ok(Response::builder()
+                    .status(status)
+                    .body(body.into_bytes())
+                    .unwrap()),
+                Err(kind) => Err(IoError::from(kind)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RetryableClient for MockClient {
+        async fn sleep(&self, dur: Duration) {
+            self.sleeps.lock().unwrap().push(dur);
+        }
+    }
+
+    fn err_body(code: i32) -> String {
+        format!(
+            r#"{{"error":{{"message":"x","type":"OAuthException","code":{code},"fbtrace_id":"x"}}}}"#
+        )
+    }
+
+    fn request(method: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri("https://graph.facebook.com/v15.0/me")
+            .body(b"BODY".to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_respond() {
+        block_on(async {
+            let policy = RetryPolicy::new()
+                .jitter(false)
+                .class_policy(
+                    ErrorClass::RateLimited,
+                    RetryClassPolicy::new(2, Duration::from_secs(60), Duration::from_secs(60)),
+                )
+                .class_policy(
+                    ErrorClass::Transient,
+                    RetryClassPolicy::new(5, Duration::from_secs(1), Duration::from_secs(30)),
+                );
+
+            //
+            let client = RetryingClient::new(
+                MockClient::new(vec![
+                    Ok((500, err_body(2))),
+                    Err(IoErrorKind::ConnectionReset),
+                    Ok((503, "".into())),
+                    Ok((200, "{}".into())),
+                ]),
+                policy.to_owned(),
+            );
+            let response = client.respond(request("GET")).await.unwrap();
+            assert_eq!(response.status(), 200);
+            // The delay follows the attempts of the same class.
+            assert_eq!(
+                *client.client().sleeps.lock().unwrap(),
+                vec![
+                    Duration::from_secs(1),
+                    Duration::from_secs(1),
+                    Duration::from_secs(2)
+                ]
+            );
+
+            //
+            let client = RetryingClient::new(
+                MockClient::new(vec![Ok((400, err_body(4))), Ok((400, err_body(4)))]),
+                policy.to_owned(),
+            );
+            let response = client.respond(request("GET")).await.unwrap();
+            assert_eq!(response.status(), 400);
+            assert_eq!(
+                *client.client().sleeps.lock().unwrap(),
+                vec![Duration::from_secs(60)]
+            );
+
+            //
+            let client = RetryingClient::new(
+                MockClient::new(vec![Ok((400, err_body(190)))]),
+                policy.to_owned(),
+            );
+            let response = client.respond(request("GET")).await.unwrap();
+            assert_eq!(response.status(), 400);
+            assert!(client.client().sleeps.lock().unwrap().is_empty());
+
+            //
+            let client = RetryingClient::new(
+                MockClient::new(vec![
+                    Err(IoErrorKind::ConnectionReset),
+                    Err(IoErrorKind::ConnectionReset),
+                    Err(IoErrorKind::TimedOut),
+                ]),
+                policy.to_owned(),
+            );
+            let err = client.respond(request("GET")).await.unwrap_err();
+            assert_eq!(err.kind(), IoErrorKind::TimedOut);
+
+            // Each class has its own budget, transient errors don't use up the network one.
+            let client = RetryingClient::new(
+                MockClient::new(vec![
+                    Ok((500, err_body(2))),
+                    Ok((500, err_body(2))),
+                    Ok((500, err_body(2))),
+                    Err(IoErrorKind::ConnectionReset),
+                    Ok((200, "{}".into())),
+                ]),
+                policy.to_owned(),
+            );
+            let response = client.respond(request("GET")).await.unwrap();
+            assert_eq!(response.status(), 200);
+            assert_eq!(
+                *client.client().sleeps.lock().unwrap(),
+                vec![
+                    Duration::from_secs(1),
+                    Duration::from_secs(2),
+                    Duration::from_secs(4),
+                    Duration::from_secs(1)
+                ]
+            );
+
+            // Network errors of a non idempotent request are not retried by default.
+            let client = RetryingClient::new(
+                MockClient::new(vec![Err(IoErrorKind::ConnectionReset)]),
+                policy.to_owned(),
+            );
+            let err = client.respond(request("POST")).await.unwrap_err();
+            assert_eq!(err.kind(), IoErrorKind::ConnectionReset);
+            assert!(client.client().sleeps.lock().unwrap().is_empty());
+
+            let client = RetryingClient::new(
+                MockClient::new(vec![
+                    Err(IoErrorKind::ConnectionReset),
+                    Ok((200, "{}".into())),
+                ]),
+                policy.to_owned().network_retry_non_idempotent(true),
+            );
+            let response = client.respond(request("POST")).await.unwrap();
+            assert_eq!(response.status(), 200);
+
+            // The same for transient errors.
+            let client = RetryingClient::new(
+                MockClient::new(vec![Ok((500, err_body(2)))]),
+                policy.to_owned(),
+            );
+            let response = client.respond(request("POST")).await.unwrap();
+            assert_eq!(response.status(), 500);
+            assert!(client.client().sleeps.lock().unwrap().is_empty());
+
+            let client = RetryingClient::new(
+                MockClient::new(vec![Ok((500, err_body(2))), Ok((200, "{}".into()))]),
+                policy.to_owned().transient_retry_non_idempotent(true),
+            );
+            let response = client.respond(request("POST")).await.unwrap();
+            assert_eq!(response.status(), 200);
+        })
+    }
+
+    #[test]
+    fn test_respond_extensions() {
+        block_on(async {
+            let client = RetryingClient::new(
+                MockClient::new(vec![Ok((500, err_body(2))), Ok((200, "{}".into()))]),
+                RetryPolicy::new().jitter(false),
+            );
+            let mut request = request("GET");
+            request.extensions_mut().insert("EXT");
+            let response = client.respond(request).await.unwrap();
+            assert_eq!(response.status(), 200);
+            assert_eq!(
+                *client.client().extensions.lock().unwrap(),
+                vec![Some("EXT"), None]
+            );
+        })
+    }
+}
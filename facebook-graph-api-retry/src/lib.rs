@@ -0,0 +1,14 @@
+//! Retries Graph API requests that failed with a rate limit, a transient error or a network error.
+//!
+//! https://developers.facebook.com/docs/graph-api/guides/error-handling
+//! https://developers.facebook.com/docs/graph-api/overview/rate-limiting
+
+pub use facebook_graph_api_object_error;
+
+pub mod class;
+pub mod client;
+pub mod policy;
+
+pub use class::ErrorClass;
+pub use client::RetryingClient;
+pub use policy::{RetryClassPolicy, RetryDecision, RetryPolicy};
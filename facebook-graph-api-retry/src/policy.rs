@@ -0,0 +1,219 @@
+use core::time::Duration;
+
+use http_api_client_endpoint::http::Method;
+use rand::Rng as _;
+
+use crate::ErrorClass;
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryClassPolicy {
+    // Including the first attempt, 1 means no retry.
+    pub max_attempts: usize,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryClassPolicy {
+    pub const fn new(max_attempts: usize, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay,
+        }
+    }
+
+    pub const fn never() -> Self {
+        Self::new(1, Duration::ZERO, Duration::ZERO)
+    }
+
+    // base_delay * 2^(attempt - 1), capped at max_delay.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let exp = attempt.saturating_sub(1).min(31) as u32;
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(exp))
+            .min(self.max_delay)
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    Retry { class: ErrorClass, after: Duration },
+    GiveUp { class: ErrorClass },
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub rate_limited: RetryClassPolicy,
+    pub transient: RetryClassPolicy,
+    pub network: RetryClassPolicy,
+    // With jitter the delay is a random value between half of and the full backoff delay.
+    pub jitter: bool,
+    // A request that failed with a network error may still have been processed,
+    // so by default only idempotent methods (e.g. GET, not POST) are retried on Network.
+    pub network_retry_non_idempotent: bool,
+    // The same for Transient, a 5xx or code 1/2 (e.g. IG media_publish 2207001) may come after the write happened.
+    pub transient_retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            rate_limited: RetryClassPolicy::new(
+                4,
+                Duration::from_secs(30),
+                Duration::from_secs(60 * 5),
+            ),
+            transient: RetryClassPolicy::new(5, Duration::from_secs(1), Duration::from_secs(30)),
+            network: RetryClassPolicy::new(3, Duration::from_secs(1), Duration::from_secs(10)),
+            jitter: true,
+            network_retry_non_idempotent: false,
+            transient_retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn class_policy(mut self, class: ErrorClass, value: RetryClassPolicy) -> Self {
+        match class {
+            ErrorClass::RateLimited => self.rate_limited = value,
+            ErrorClass::Transient => self.transient = value,
+            ErrorClass::Network => self.network = value,
+            ErrorClass::Permanent => {}
+        }
+        self
+    }
+
+    pub fn jitter(mut self, value: bool) -> Self {
+        self.jitter = value;
+        self
+    }
+
+    pub fn network_retry_non_idempotent(mut self, value: bool) -> Self {
+        self.network_retry_non_idempotent = value;
+        self
+    }
+
+    pub fn transient_retry_non_idempotent(mut self, value: bool) -> Self {
+        self.transient_retry_non_idempotent = value;
+        self
+    }
+
+    pub fn is_retryable_method(&self, class: ErrorClass, method: &Method) -> bool {
+        match class {
+            ErrorClass::Network => self.network_retry_non_idempotent || method.is_idempotent(),
+            ErrorClass::Transient => self.transient_retry_non_idempotent || method.is_idempotent(),
+            ErrorClass::RateLimited | ErrorClass::Permanent => true,
+        }
+    }
+
+    pub fn get_class_policy(&self, class: ErrorClass) -> RetryClassPolicy {
+        match class {
+            ErrorClass::RateLimited => self.rate_limited,
+            ErrorClass::Transient => self.transient,
+            ErrorClass::Network => self.network,
+            ErrorClass::Permanent => RetryClassPolicy::never(),
+        }
+    }
+
+    // attempt is the number of attempts that failed with class so far, starting at 1.
+    pub fn decide(&self, class: ErrorClass, attempt: usize) -> RetryDecision {
+        let class_policy = self.get_class_policy(class);
+        if attempt >= class_policy.max_attempts {
+            return RetryDecision::GiveUp { class };
+        }
+
+        let mut after = class_policy.delay(attempt);
+        if self.jitter && !after.is_zero() {
+            after = rand::thread_rng().gen_range(after / 2..=after);
+        }
+
+        RetryDecision::Retry { class, after }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let class_policy =
+            RetryClassPolicy::new(10, Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(class_policy.delay(1), Duration::from_secs(1));
+        assert_eq!(class_policy.delay(2), Duration::from_secs(2));
+        assert_eq!(class_policy.delay(4), Duration::from_secs(8));
+        assert_eq!(class_policy.delay(5), Duration::from_secs(10));
+        assert_eq!(class_policy.delay(100), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_decide() {
+        let policy = RetryPolicy::new().jitter(false).class_policy(
+            ErrorClass::Transient,
+            RetryClassPolicy::new(3, Duration::from_secs(1), Duration::from_secs(30)),
+        );
+
+        assert_eq!(
+            policy.decide(ErrorClass::Transient, 1),
+            RetryDecision::Retry {
+                class: ErrorClass::Transient,
+                after: Duration::from_secs(1)
+            }
+        );
+        assert_eq!(
+            policy.decide(ErrorClass::Transient, 2),
+            RetryDecision::Retry {
+                class: ErrorClass::Transient,
+                after: Duration::from_secs(2)
+            }
+        );
+        assert_eq!(
+            policy.decide(ErrorClass::Transient, 3),
+            RetryDecision::GiveUp {
+                class: ErrorClass::Transient
+            }
+        );
+        assert_eq!(
+            policy.decide(ErrorClass::Permanent, 1),
+            RetryDecision::GiveUp {
+                class: ErrorClass::Permanent
+            }
+        );
+
+        let policy = RetryPolicy::new();
+        for _ in 0..100 {
+            match policy.decide(ErrorClass::RateLimited, 2) {
+                RetryDecision::Retry { after, .. } => {
+                    assert!(after >= Duration::from_secs(30) && after <= Duration::from_secs(60))
+                }
+                x => panic!("{x:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_retryable_method() {
+        let policy = RetryPolicy::new();
+        assert!(policy.is_retryable_method(ErrorClass::Network, &Method::GET));
+        assert!(!policy.is_retryable_method(ErrorClass::Network, &Method::POST));
+        assert!(policy.is_retryable_method(ErrorClass::Transient, &Method::GET));
+        assert!(!policy.is_retryable_method(ErrorClass::Transient, &Method::POST));
+        assert!(policy.is_retryable_method(ErrorClass::RateLimited, &Method::POST));
+
+        let policy = policy.network_retry_non_idempotent(true);
+        assert!(policy.is_retryable_method(ErrorClass::Network, &Method::POST));
+        assert!(!policy.is_retryable_method(ErrorClass::Transient, &Method::POST));
+
+        let policy = policy.transient_retry_non_idempotent(true);
+        assert!(policy.is_retryable_method(ErrorClass::Transient, &Method::POST));
+    }
+}
@@ -23,9 +23,21 @@ pub enum EndpointError {
     Other(Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
+// Does not include the serde_json message, it may quote the response body which can contain access tokens.
 impl core::fmt::Display for EndpointError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Self::MakeRequestUrlFailed(err) => write!(f, "MakeRequestUrlFailed({err})"),
+            Self::MakeRequestFailed(err) => write!(f, "MakeRequestFailed({err})"),
+            Self::DeResponseBodyOkJsonFailed(err) => write!(
+                f,
+                "DeResponseBodyOkJsonFailed({:?} at line {} column {})",
+                err.classify(),
+                err.line(),
+                err.column()
+            ),
+            Self::Other(err) => write!(f, "Other({err})"),
+        }
     }
 }
 
@@ -1,4 +1,6 @@
+use facebook_access_token::{secret::redact, AppSecretProof};
 use facebook_graph_api_object_paging::cursor_based_pagination::Paging;
+use facebook_graph_api_rate_limit::Usage;
 use http_api_client_endpoint::{
     http::{
         header::{ACCEPT, USER_AGENT},
@@ -18,16 +20,32 @@ use crate::{
 };
 
 //
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SearchEndpoint {
     pub q: Box<str>,
     pub limit: Option<usize>,
     pub after: Option<Box<str>>,
     //
     pub access_token: Box<str>,
+    pub app_secret: Option<Box<str>>,
+    pub app_secret_time: Option<u64>,
     pub version: Option<Box<str>>,
 }
 
+impl core::fmt::Debug for SearchEndpoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SearchEndpoint")
+            .field("q", &self.q)
+            .field("limit", &self.limit)
+            .field("after", &self.after)
+            .field("access_token", &redact(&self.access_token))
+            .field("app_secret", &self.app_secret.as_deref().map(redact))
+            .field("app_secret_time", &self.app_secret_time)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
 impl SearchEndpoint {
     pub fn new(
         q: impl AsRef<str>,
@@ -39,6 +57,8 @@ impl SearchEndpoint {
             limit: None,
             after: None,
             access_token: access_token.as_ref().into(),
+            app_secret: None,
+            app_secret_time: None,
             version: version.into(),
         }
     }
@@ -52,6 +72,16 @@ impl SearchEndpoint {
         self.after = Some(value.as_ref().into());
         self
     }
+
+    pub fn app_secret(mut self, value: impl AsRef<str>) -> Self {
+        self.app_secret = Some(value.as_ref().into());
+        self
+    }
+
+    pub fn app_secret_time(mut self, value: u64) -> Self {
+        self.app_secret_time = Some(value);
+        self
+    }
 }
 
 impl Endpoint for SearchEndpoint {
@@ -73,6 +103,16 @@ impl Endpoint for SearchEndpoint {
 
         url.query_pairs_mut()
             .append_pair("access_token", &self.access_token);
+        if let Some(app_secret) = &self.app_secret {
+            url.query_pairs_mut().extend_pairs(
+                AppSecretProof::with_optional_time(
+                    &self.access_token,
+                    app_secret,
+                    self.app_secret_time,
+                )
+                .pairs(),
+            );
+        }
         if let Some(limit) = self.limit {
             url.query_pairs_mut()
                 .append_pair("limit", limit.to_string().as_str());
@@ -97,13 +137,20 @@ impl Endpoint for SearchEndpoint {
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
         let status = response.status();
+        let usage = Usage::from_headers(response.headers());
         match status {
-            StatusCode::OK => Ok(EndpointRet::Ok(
-                serde_json::from_slice(response.body())
-                    .map_err(EndpointError::DeResponseBodyOkJsonFailed)?,
-            )),
+            StatusCode::OK => {
+                let mut ok_json: SearchEndpointResponseBodyOkJson =
+                    serde_json::from_slice(response.body())
+                        .map_err(EndpointError::DeResponseBodyOkJsonFailed)?;
+                ok_json.usage = usage;
+                Ok(EndpointRet::Ok(ok_json))
+            }
             status => match serde_json::from_slice::<ResponseBodyErrJson>(response.body()) {
-                Ok(err_json) => Ok(EndpointRet::Other((status, Ok(err_json)))),
+                Ok(mut err_json) => {
+                    err_json.usage = usage.into();
+                    Ok(EndpointRet::Other((status, Ok(err_json))))
+                }
                 Err(_) => Ok(EndpointRet::Other((
                     status,
                     Err(response.body().to_owned()),
@@ -120,6 +167,9 @@ impl Endpoint for SearchEndpoint {
 pub struct SearchEndpointResponseBodyOkJson {
     pub data: Vec<PageForSearchEndpoint>,
     pub paging: Option<Paging>,
+    //
+    #[serde(skip)]
+    pub usage: Usage,
 }
 
 #[cfg(test)]
@@ -162,5 +212,38 @@ mod tests {
             req.uri().path_and_query().unwrap(),
             "/v15.0/pages/search?fields=id,name,location{city,country,latitude,longitude,state,street,zip},link,is_eligible_for_branded_content,is_unclaimed,verification_status&q=Chengdu&access_token=ACCESS_TOKEN&limit=30&after=AFTER"
         );
+
+        let ep = SearchEndpoint::new("Chengdu", "ACCESS_TOKEN", None)
+            .app_secret("APP_SECRET")
+            .app_secret_time(1672531200);
+        let req = ep.render_request().unwrap();
+        assert_eq!(
+            req.uri().path_and_query().unwrap(),
+            "/v15.0/pages/search?fields=id,name,location{city,country,latitude,longitude,state,street,zip},link,is_eligible_for_branded_content,is_unclaimed,verification_status&q=Chengdu&access_token=ACCESS_TOKEN&appsecret_proof=61eb6bf9d00cce03ecea8b29edc32d8dec9d3d4a2608f357cc9734efd63fa581&appsecret_time=1672531200"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_parse_response() {
+        let ep = SearchEndpoint::new("Chengdu", "ACCESS_TOKEN", None);
+        let response = Response::builder()
+            .status(400)
+            .header(
+                "X-App-Usage",
+                r#"{"call_count":100,"total_time":25,"total_cputime":25}"#,
+            )
+            .body(
+                br#"{"error":{"message":"(#4) Application request limit reached","type":"OAuthException","is_transient":true,"code":4,"fbtrace_id":"x"}}"#
+                    .to_vec(),
+            )
+            .unwrap();
+        match ep.parse_response(response).unwrap() {
+            EndpointRet::Other((status_code, Ok(err_json))) => {
+                assert_eq!(status_code, StatusCode::BAD_REQUEST);
+                assert_eq!(err_json.error.code, 4);
+                assert_eq!(err_json.usage.app.unwrap().call_count, 100);
+            }
+            x => panic!("{x:?}"),
+        }
     }
 }
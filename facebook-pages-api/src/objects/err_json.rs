@@ -1,8 +1,22 @@
 use facebook_graph_api_object_error::Error;
+use facebook_graph_api_rate_limit::Usage;
 use serde::{Deserialize, Serialize};
 
 //
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ErrJson {
     pub error: Error,
+    // Only filled by the endpoints that parse the usage headers,
+    // e.g. to see how close to the limit the app was when it got ApiTooManyCalls.
+    #[serde(skip)]
+    pub usage: Box<Usage>,
+}
+
+impl ErrJson {
+    pub fn new(error: Error) -> Self {
+        Self {
+            error,
+            usage: Default::default(),
+        }
+    }
 }